@@ -0,0 +1,274 @@
+use crate::impls::Forward;
+use proc_macro2::{Ident, Span, TokenStream};
+use syn::meta::ParseNestedMeta;
+use syn::parse::{Parse, Parser};
+use syn::spanned::Spanned as _;
+use syn::{parenthesized, DeriveInput, Error, Expr, Field, Result, Token, Type, Visibility};
+
+// Options given in #[ref_cast(...)] on the type. Options that are either
+// present or not record the span where they were given.
+#[derive(Default)]
+pub struct Attrs {
+    pub borrow: Vec<Type>,
+    pub forward: Vec<Forward>,
+    pub compare: Vec<Forward>,
+    pub as_ref: Option<Span>,
+    pub from_ref: Option<Span>,
+    pub deref: Option<Span>,
+    pub slice: Option<Span>,
+    pub wrap: Option<Span>,
+    pub pin: Option<Span>,
+    pub via: Vec<Type>,
+    pub cast_from: Vec<Type>,
+    pub unsize: Option<Span>,
+    pub unsize_dyn: Option<Type>,
+    pub capability: Option<Type>,
+    pub scope: Option<Visibility>,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Derive {
+    RefCast,
+    RefCastCustom,
+}
+
+pub fn get(input: &DeriveInput, derive: Derive) -> Result<Attrs> {
+    let mut attrs = Attrs::default();
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("ref_cast") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            check_supported(&meta, derive)?;
+            if meta.path.is_ident("borrow") {
+                parse_types(&meta, &mut attrs.borrow)
+            } else if meta.path.is_ident("forward") {
+                parse_forward(&meta, &mut attrs.forward)
+            } else if meta.path.is_ident("compare") {
+                parse_compare(&meta, &mut attrs.compare)
+            } else if meta.path.is_ident("as_ref") {
+                attrs.as_ref = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("from_ref") {
+                attrs.from_ref = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("deref") {
+                attrs.deref = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("slice") {
+                attrs.slice = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("wrap") {
+                attrs.wrap = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("pin") {
+                attrs.pin = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("cast_from") {
+                parse_types(&meta, &mut attrs.cast_from)
+            } else if meta.path.is_ident("unsize") {
+                parse_unsize(&meta, &mut attrs)
+            } else if meta.path.is_ident("capability") {
+                parse_capability(&meta, &mut attrs.capability)
+            } else if meta.path.is_ident("scope") {
+                parse_scope(&meta, &mut attrs.scope)
+            } else if meta.path.is_ident("via") {
+                attrs.via.push(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported ref_cast attribute"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}
+
+// Of the options, via and cast_from apply to both derives, capability and scope
+// only to derive(RefCastCustom), and the rest only to derive(RefCast).
+fn check_supported(meta: &ParseNestedMeta, derive: Derive) -> Result<()> {
+    let Some(option) = meta.path.get_ident() else {
+        return Ok(());
+    };
+    let supported_by = match option.to_string().as_str() {
+        "capability" | "scope" => Derive::RefCastCustom,
+        "borrow" | "forward" | "compare" | "as_ref" | "from_ref" | "deref" | "slice" | "wrap"
+        | "pin" | "unsize" => Derive::RefCast,
+        _ => return Ok(()),
+    };
+    if derive == supported_by {
+        return Ok(());
+    }
+    let supported_by = match supported_by {
+        Derive::RefCast => "RefCast",
+        Derive::RefCastCustom => "RefCastCustom",
+    };
+    Err(meta.error(format_args!(
+        "ref_cast({option}) is only supported by derive({supported_by})",
+    )))
+}
+
+fn parse_types(meta: &ParseNestedMeta, types: &mut Vec<Type>) -> Result<()> {
+    let content;
+    parenthesized!(content in meta.input);
+    types.extend(content.parse_terminated(Type::parse, Token![,])?);
+    Ok(())
+}
+
+fn parse_forward(meta: &ParseNestedMeta, forward: &mut Vec<Forward>) -> Result<()> {
+    let content;
+    parenthesized!(content in meta.input);
+    for ident in content.parse_terminated(Ident::parse, Token![,])? {
+        match Forward::from_ident(&ident) {
+            Some(trait_) if forward.contains(&trait_) => {}
+            Some(trait_) => forward.push(trait_),
+            None => {
+                return Err(Error::new(
+                    ident.span(),
+                    "unsupported trait for ref_cast(forward)",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_compare(meta: &ParseNestedMeta, compare: &mut Vec<Forward>) -> Result<()> {
+    let content;
+    parenthesized!(content in meta.input);
+    for ident in content.parse_terminated(Ident::parse, Token![,])? {
+        match Forward::from_ident(&ident) {
+            Some(trait_) if compare.contains(&trait_) => {}
+            Some(trait_ @ (Forward::PartialEq | Forward::PartialOrd)) => compare.push(trait_),
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "unsupported trait for ref_cast(compare), expected PartialEq or PartialOrd",
+                ));
+            }
+        }
+    }
+    // PartialOrd<Rhs> requires PartialEq<Rhs>.
+    if compare.contains(&Forward::PartialOrd) && !compare.contains(&Forward::PartialEq) {
+        compare.push(Forward::PartialEq);
+    }
+    Ok(())
+}
+
+fn parse_unsize(meta: &ParseNestedMeta, attrs: &mut Attrs) -> Result<()> {
+    if !meta.input.peek(Token![=]) {
+        attrs.unsize = Some(meta.path.span());
+        return Ok(());
+    }
+    let ty: Type = meta.value()?.parse()?;
+    if !matches!(ty, Type::TraitObject(_)) {
+        return Err(Error::new_spanned(
+            ty,
+            "expected a trait object type, like ref_cast(unsize = dyn Trait)",
+        ));
+    }
+    if attrs.unsize_dyn.is_some() {
+        return Err(meta.error("duplicate ref_cast(unsize = ...) attribute"));
+    }
+    attrs.unsize_dyn = Some(ty);
+    Ok(())
+}
+
+fn parse_capability(meta: &ParseNestedMeta, capability: &mut Option<Type>) -> Result<()> {
+    if capability.is_some() {
+        return Err(meta.error("duplicate ref_cast(capability = ...) attribute"));
+    }
+    *capability = Some(meta.value()?.parse()?);
+    Ok(())
+}
+
+fn parse_scope(meta: &ParseNestedMeta, scope: &mut Option<Visibility>) -> Result<()> {
+    if scope.is_some() {
+        return Err(meta.error("duplicate ref_cast(scope = ...) attribute"));
+    }
+    let vis: Visibility = meta.value()?.parse()?;
+    if let Visibility::Inherited = vis {
+        return Err(meta.error("expected a visibility, like ref_cast(scope = pub(self))"));
+    }
+    *scope = Some(vis);
+    Ok(())
+}
+
+pub fn is_from(field: &Field) -> Result<bool> {
+    let mut is_from = false;
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("ref_cast") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("from") {
+                is_from = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported ref_cast field attribute"))
+            }
+        })?;
+    }
+
+    Ok(is_from)
+}
+
+// Arguments of #[ref_cast_custom(...)]: a validity check, and the functions to
+// generate in addition to the one being declared.
+pub struct CustomArgs {
+    pub check: Option<Expr>,
+    pub mut_fn: Option<Ident>,
+    pub box_fn: Option<Ident>,
+    pub rc_fn: Option<Ident>,
+    pub arc_fn: Option<Ident>,
+}
+
+impl CustomArgs {
+    pub fn has_family(&self) -> bool {
+        self.mut_fn.is_some()
+            || self.box_fn.is_some()
+            || self.rc_fn.is_some()
+            || self.arc_fn.is_some()
+    }
+}
+
+pub fn custom_args(args: TokenStream) -> Result<CustomArgs> {
+    let mut custom_args = CustomArgs {
+        check: None,
+        mut_fn: None,
+        box_fn: None,
+        rc_fn: None,
+        arc_fn: None,
+    };
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("check") {
+            if custom_args.check.is_some() {
+                return Err(meta.error("duplicate ref_cast_custom argument"));
+            }
+            custom_args.check = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        let slot = if meta.path.is_ident("mut") {
+            &mut custom_args.mut_fn
+        } else if meta.path.is_ident("boxed") {
+            &mut custom_args.box_fn
+        } else if meta.path.is_ident("rc") {
+            &mut custom_args.rc_fn
+        } else if meta.path.is_ident("arc") {
+            &mut custom_args.arc_fn
+        } else {
+            return Err(meta.error("unsupported ref_cast_custom argument"));
+        };
+        if slot.is_some() {
+            return Err(meta.error("duplicate ref_cast_custom argument"));
+        }
+        *slot = Some(meta.value()?.parse()?);
+        Ok(())
+    });
+    parser.parse2(args)?;
+
+    Ok(custom_args)
+}
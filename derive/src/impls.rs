@@ -0,0 +1,799 @@
+use crate::private;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, DeriveInput, Error, Field, GenericArgument, GenericParam, Generics, Lifetime,
+    Member, PathArguments, Result, Token, Type, WhereClause, WherePredicate,
+};
+
+pub struct Wrapper<'a> {
+    pub input: &'a DeriveInput,
+    pub variant: Option<&'a Ident>,
+    pub member: Member,
+    pub from: &'a Type,
+}
+
+impl Wrapper<'_> {
+    // Expression of type &From, given an expression of type &Wrapper.
+    pub fn field(&self, receiver: TokenStream) -> TokenStream {
+        let name = &self.input.ident;
+        let member = &self.member;
+        match self.variant {
+            None => quote!((&#receiver.#member)),
+            Some(variant) => quote! {
+                (match #receiver {
+                    #name::#variant { #member: field, .. } => field,
+                })
+            },
+        }
+    }
+
+    // Expression of type &mut From, given an expression of type &mut Wrapper.
+    pub fn field_mut(&self, receiver: TokenStream) -> TokenStream {
+        let member = &self.member;
+        match self.variant {
+            None => quote!((&mut #receiver.#member)),
+            Some(_) => self.field(receiver),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Forward {
+    Debug,
+    Display,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+}
+
+impl Forward {
+    pub const BORROW: [Self; 5] = [
+        Forward::PartialEq,
+        Forward::Eq,
+        Forward::PartialOrd,
+        Forward::Ord,
+        Forward::Hash,
+    ];
+
+    pub fn from_ident(ident: &Ident) -> Option<Self> {
+        Some(match ident.to_string().as_str() {
+            "Debug" => Forward::Debug,
+            "Display" => Forward::Display,
+            "PartialEq" => Forward::PartialEq,
+            "Eq" => Forward::Eq,
+            "PartialOrd" => Forward::PartialOrd,
+            "Ord" => Forward::Ord,
+            "Hash" => Forward::Hash,
+            _ => return None,
+        })
+    }
+
+    fn path(self) -> TokenStream {
+        match self {
+            Forward::Debug => quote!(::core::fmt::Debug),
+            Forward::Display => quote!(::core::fmt::Display),
+            Forward::PartialEq => quote!(::core::cmp::PartialEq),
+            Forward::Eq => quote!(::core::cmp::Eq),
+            Forward::PartialOrd => quote!(::core::cmp::PartialOrd),
+            Forward::Ord => quote!(::core::cmp::Ord),
+            Forward::Hash => quote!(::core::hash::Hash),
+        }
+    }
+}
+
+pub fn ref_cast_from(wrapper: &Wrapper) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, where_clause) = wrapper.input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::ref_cast::RefCastFrom<#from> for #name #ty_generics #where_clause {
+            #[inline]
+            fn ref_cast_from(from: &#from) -> &Self {
+                <Self as ::ref_cast::RefCast>::ref_cast(from)
+            }
+
+            #[inline]
+            fn ref_cast_from_mut(from: &mut #from) -> &mut Self {
+                <Self as ::ref_cast::RefCast>::ref_cast_mut(from)
+            }
+        }
+    }
+}
+
+// For derive(RefCastCustom), which has no RefCast impl to go through.
+pub fn ref_cast_from_custom(wrapper: &Wrapper, trivial: &[&Type], sources: &[Type]) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let name_str = name.to_string();
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, where_clause) = wrapper.input.generics.split_for_impl();
+
+    let assert_layout = quote! {
+        #[cfg(debug_assertions)]
+        {
+            #[allow(unused_imports)]
+            use ::ref_cast::#private::LayoutUnsized;
+            ::ref_cast::#private::assert_layout::<Self, #from>(
+                #name_str,
+                ::ref_cast::#private::Layout::<Self>::SIZE,
+                ::ref_cast::#private::Layout::<#from>::SIZE,
+                ::ref_cast::#private::Layout::<Self>::ALIGN,
+                ::ref_cast::#private::Layout::<#from>::ALIGN,
+            );
+            let _from: &#from = &*_from;
+            let _outer = unsafe { &*(_from as *const #from as *const Self) };
+            ::ref_cast::#private::assert_layout_of_val::<Self, #from>(#name_str, _outer, _from);
+        }
+    };
+
+    let private2 = private;
+    let assert_trivial_fields = if !trivial.is_empty() {
+        Some(quote! {
+            if false {
+                #(
+                    ::ref_cast::#private2::assert_trivial::<#trivial>();
+                )*
+            }
+        })
+    } else {
+        None
+    };
+
+    // Unlike derive(RefCast), there is no RefCastFrom impl for the field type
+    // to go through, so each listed type gets a cast of its own.
+    let mut expanded = TokenStream::new();
+    for source in sources {
+        let (where_clause, deref, deref_mut) = if same_type(source, from) {
+            (where_clause.cloned(), quote!(from), quote!(from))
+        } else {
+            (
+                Some(bounded(
+                    &wrapper.input.generics,
+                    parse_quote!(#source: ::core::ops::DerefMut<Target = #from>),
+                )),
+                quote!(::core::ops::Deref::deref(from)),
+                quote!(::core::ops::DerefMut::deref_mut(from)),
+            )
+        };
+        expanded.extend(quote! {
+            impl #impl_generics ::ref_cast::RefCastFrom<#source> for #name #ty_generics #where_clause {
+                #[inline]
+                fn ref_cast_from(from: &#source) -> &Self {
+                    let _from: &#from = #deref;
+                    #assert_trivial_fields
+                    #assert_layout
+                    unsafe {
+                        &*(_from as *const #from as *const Self)
+                    }
+                }
+
+                #[inline]
+                fn ref_cast_from_mut(from: &mut #source) -> &mut Self {
+                    let _from: &mut #from = #deref_mut;
+                    #assert_layout
+                    unsafe {
+                        &mut *(_from as *mut #from as *mut Self)
+                    }
+                }
+            }
+        });
+    }
+    expanded
+}
+
+pub fn cast_from(wrapper: &Wrapper, sources: &[Type]) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, _) = wrapper.input.generics.split_for_impl();
+
+    let mut expanded = TokenStream::new();
+    for source in sources {
+        if same_type(source, from) {
+            continue;
+        }
+        let where_clause = bounded(
+            &wrapper.input.generics,
+            parse_quote!(#source: ::core::ops::DerefMut<Target = #from>),
+        );
+        expanded.extend(quote! {
+            impl #impl_generics ::ref_cast::RefCastFrom<#source> for #name #ty_generics #where_clause {
+                #[inline]
+                fn ref_cast_from(from: &#source) -> &Self {
+                    <Self as ::ref_cast::RefCastFrom<#from>>::ref_cast_from(
+                        ::core::ops::Deref::deref(from),
+                    )
+                }
+
+                #[inline]
+                fn ref_cast_from_mut(from: &mut #source) -> &mut Self {
+                    <Self as ::ref_cast::RefCastFrom<#from>>::ref_cast_from_mut(
+                        ::core::ops::DerefMut::deref_mut(from),
+                    )
+                }
+            }
+        });
+    }
+    expanded
+}
+
+pub fn via(wrapper: &Wrapper, via: &[Type]) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let (impl_generics, ty_generics, _) = wrapper.input.generics.split_for_impl();
+
+    let mut expanded = TokenStream::new();
+    for inner in via {
+        let mut generics = wrapper.input.generics.clone();
+        let where_clause = generics.make_where_clause();
+        where_clause
+            .predicates
+            .push(parse_quote!(#inner: ::ref_cast::RefCast));
+        where_clause
+            .predicates
+            .push(parse_quote!(#name #ty_generics: ::ref_cast::RefCastFrom<#inner>));
+        let source = source_of(inner);
+        expanded.extend(quote! {
+            impl #impl_generics ::ref_cast::RefCastFrom<#source> for #name #ty_generics #where_clause {
+                #[inline]
+                fn ref_cast_from(from: &#source) -> &Self {
+                    <Self as ::ref_cast::RefCastFrom<#inner>>::ref_cast_from(
+                        <#inner as ::ref_cast::RefCast>::ref_cast(from),
+                    )
+                }
+
+                #[inline]
+                fn ref_cast_from_mut(from: &mut #source) -> &mut Self {
+                    <Self as ::ref_cast::RefCastFrom<#inner>>::ref_cast_from_mut(
+                        <#inner as ::ref_cast::RefCast>::ref_cast_mut(from),
+                    )
+                }
+            }
+        });
+    }
+    expanded
+}
+
+pub fn borrow(wrapper: &Wrapper, sources: &[Type]) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, _) = wrapper.input.generics.split_for_impl();
+
+    let mut expanded = TokenStream::new();
+    for source in sources {
+        let where_clause = bounded(
+            &wrapper.input.generics,
+            parse_quote!(#source: ::core::borrow::Borrow<#from>),
+        );
+        expanded.extend(quote! {
+            impl #impl_generics ::core::borrow::Borrow<#name #ty_generics> for #source #where_clause {
+                #[inline]
+                fn borrow(&self) -> &#name #ty_generics {
+                    <#name #ty_generics as ::ref_cast::RefCast>::ref_cast(
+                        <#source as ::core::borrow::Borrow<#from>>::borrow(self),
+                    )
+                }
+            }
+        });
+    }
+    expanded
+}
+
+pub fn forward(wrapper: &Wrapper, forward: &[Forward]) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let self_field = wrapper.field(quote!(self));
+    let other_field = wrapper.field(quote!(other));
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, _) = wrapper.input.generics.split_for_impl();
+
+    let mut expanded = TokenStream::new();
+    for &trait_ in forward {
+        let path = trait_.path();
+        let body = match trait_ {
+            Forward::Debug | Forward::Display => quote! {
+                #[inline]
+                fn fmt(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    #path::fmt(#self_field, formatter)
+                }
+            },
+            Forward::PartialEq => quote! {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    ::core::cmp::PartialEq::eq(#self_field, #other_field)
+                }
+            },
+            Forward::Eq => TokenStream::new(),
+            Forward::PartialOrd => quote! {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                    ::core::cmp::PartialOrd::partial_cmp(#self_field, #other_field)
+                }
+            },
+            Forward::Ord => quote! {
+                #[inline]
+                fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                    ::core::cmp::Ord::cmp(#self_field, #other_field)
+                }
+            },
+            Forward::Hash => quote! {
+                #[inline]
+                fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                    ::core::hash::Hash::hash(#self_field, state);
+                }
+            },
+        };
+        let where_clause = bounded(&wrapper.input.generics, parse_quote!(#from: #path));
+        expanded.extend(quote! {
+            impl #impl_generics #path for #name #ty_generics #where_clause {
+                #body
+            }
+        });
+    }
+    expanded
+}
+
+pub fn as_ref(wrapper: &Wrapper) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let self_field = wrapper.field(quote!(self));
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, where_clause) = wrapper.input.generics.split_for_impl();
+
+    // Coherence rejects `impl<T> AsRef<Wrapper<T>> for T`, and likewise for
+    // `Box<T>` or `&T`.
+    let reverse = if !has_uncovered_type_param(from, &wrapper.input.generics) {
+        Some(quote! {
+            impl #impl_generics ::core::convert::AsRef<#name #ty_generics> for #from #where_clause {
+                #[inline]
+                fn as_ref(&self) -> &#name #ty_generics {
+                    <#name #ty_generics as ::ref_cast::RefCast>::ref_cast(self)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    quote! {
+        impl #impl_generics ::core::convert::AsRef<#from> for #name #ty_generics #where_clause {
+            #[inline]
+            fn as_ref(&self) -> &#from {
+                #self_field
+            }
+        }
+
+        #reverse
+    }
+}
+
+pub fn from(wrapper: &Wrapper) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let from = wrapper.from;
+    let (_, ty_generics, _) = wrapper.input.generics.split_for_impl();
+
+    let (lifetime, generics) = with_lifetime(&wrapper.input.generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::convert::From<&#lifetime #from> for &#lifetime #name #ty_generics #where_clause {
+            #[inline]
+            fn from(from: &#lifetime #from) -> Self {
+                <#name #ty_generics as ::ref_cast::RefCast>::ref_cast(from)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#lifetime mut #from> for &#lifetime mut #name #ty_generics #where_clause {
+            #[inline]
+            fn from(from: &#lifetime mut #from) -> Self {
+                <#name #ty_generics as ::ref_cast::RefCast>::ref_cast_mut(from)
+            }
+        }
+    }
+}
+
+pub fn deref(wrapper: &Wrapper) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let self_field = wrapper.field(quote!(self));
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, where_clause) = wrapper.input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::ops::Deref for #name #ty_generics #where_clause {
+            type Target = #from;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                #self_field
+            }
+        }
+    }
+}
+
+pub fn wrap(wrapper: &Wrapper, fields: &Punctuated<Field, Token![,]>) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let vis = &wrapper.input.vis;
+    let member = &wrapper.member;
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, where_clause) = wrapper.input.generics.split_for_impl();
+
+    let path = if let Some(variant) = wrapper.variant {
+        quote!(Self::#variant)
+    } else {
+        quote!(Self)
+    };
+
+    // Every field other than the one being wrapped is trivial.
+    let trivial = fields.iter().enumerate().filter_map(|(i, field)| {
+        let trivial_member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        if trivial_member == *member {
+            return None;
+        }
+        let ty = &field.ty;
+        Some(quote! {
+            #trivial_member: <#ty as ::ref_cast::#private::Trivial>::VALUE
+        })
+    });
+
+    quote! {
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[inline]
+            #vis fn wrap(from: #from) -> Self
+            where
+                #from: ::core::marker::Sized,
+            {
+                #path {
+                    #member: from,
+                    #(#trivial,)*
+                }
+            }
+
+            #[inline]
+            #vis fn into_inner(self) -> #from
+            where
+                #from: ::core::marker::Sized,
+            {
+                match self {
+                    #path { #member: from, .. } => from,
+                }
+            }
+        }
+    }
+}
+
+pub fn pin(wrapper: &Wrapper, trivial: &[&Type]) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let vis = &wrapper.input.vis;
+    let from = wrapper.from;
+    let field_mut = wrapper.field_mut(quote!(wrapper));
+    let (impl_generics, ty_generics, where_clause) = wrapper.input.generics.split_for_impl();
+    let (lifetime, lifetime_generics) = with_lifetime(&wrapper.input.generics);
+    let (lifetime_impl_generics, _, _) = lifetime_generics.split_for_impl();
+
+    let mut unpin_generics = wrapper.input.generics.clone();
+    let unpin_where_clause = unpin_generics.make_where_clause();
+    for ty in Some(&from).into_iter().chain(trivial) {
+        unpin_where_clause.predicates.push(parse_quote! {
+            ::ref_cast::#private::PinnedField<#lifetime, #ty>: ::core::marker::Unpin
+        });
+    }
+
+    quote! {
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[inline]
+            #vis fn project(self: ::core::pin::Pin<&mut Self>) -> ::core::pin::Pin<&mut #from> {
+                unsafe { ::core::pin::Pin::map_unchecked_mut(self, |wrapper| #field_mut) }
+            }
+
+            #[inline]
+            #vis fn pin_ref_cast(from: ::core::pin::Pin<&mut #from>) -> ::core::pin::Pin<&mut Self> {
+                unsafe {
+                    ::core::pin::Pin::map_unchecked_mut(
+                        from,
+                        <Self as ::ref_cast::RefCast>::ref_cast_mut,
+                    )
+                }
+            }
+        }
+
+        const _: () = {
+            // The field is pinned whenever the wrapper is, so the wrapper may
+            // only be Unpin if all of its fields are.
+            impl #lifetime_impl_generics ::core::marker::Unpin for #name #ty_generics #unpin_where_clause {}
+
+            // A Drop impl could move the field out of a pinned wrapper.
+            trait MustNotImplDrop {}
+            #[allow(drop_bounds)]
+            impl<T: ?::core::marker::Sized + ::core::ops::Drop> MustNotImplDrop for T {}
+            impl #impl_generics MustNotImplDrop for #name #ty_generics #where_clause {}
+        };
+    }
+}
+
+pub fn compare(wrapper: &Wrapper, compare: &[Forward]) -> TokenStream {
+    let name = &wrapper.input.ident;
+    let self_field = wrapper.field(quote!(self));
+    let other_field = wrapper.field(quote!(other));
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, _) = wrapper.input.generics.split_for_impl();
+    let (lifetime, lifetime_generics) = with_lifetime(&wrapper.input.generics);
+    let (lifetime_impl_generics, _, _) = lifetime_generics.split_for_impl();
+
+    // Coherence rejects impls for the field type when it leaves a type
+    // parameter uncovered, as in `T`, `&T` or `Box<T>`.
+    let reverse = !has_uncovered_type_param(from, &wrapper.input.generics);
+
+    let mut expanded = TokenStream::new();
+    for &trait_ in compare {
+        let (path, method, ret) = match trait_ {
+            Forward::PartialEq => (quote!(::core::cmp::PartialEq), quote!(eq), quote!(bool)),
+            Forward::PartialOrd => (
+                quote!(::core::cmp::PartialOrd),
+                quote!(partial_cmp),
+                quote!(::core::option::Option<::core::cmp::Ordering>),
+            ),
+            _ => unreachable!(),
+        };
+        let where_clause = bounded(&wrapper.input.generics, parse_quote!(#from: #path));
+        let lifetime_where_clause = bounded(&lifetime_generics, parse_quote!(#from: #path));
+
+        expanded.extend(quote! {
+            impl #impl_generics #path<#from> for #name #ty_generics #where_clause {
+                #[inline]
+                fn #method(&self, other: &#from) -> #ret {
+                    #path::#method(#self_field, other)
+                }
+            }
+
+            impl #lifetime_impl_generics #path<&#lifetime #from> for #name #ty_generics #lifetime_where_clause {
+                #[inline]
+                fn #method(&self, other: &&#lifetime #from) -> #ret {
+                    #path::#method(#self_field, *other)
+                }
+            }
+        });
+
+        if reverse {
+            expanded.extend(quote! {
+                impl #impl_generics #path<#name #ty_generics> for #from #where_clause {
+                    #[inline]
+                    fn #method(&self, other: &#name #ty_generics) -> #ret {
+                        #path::#method(self, #other_field)
+                    }
+                }
+
+                impl #lifetime_impl_generics #path<#name #ty_generics> for &#lifetime #from #lifetime_where_clause {
+                    #[inline]
+                    fn #method(&self, other: &#name #ty_generics) -> #ret {
+                        #path::#method(*self, #other_field)
+                    }
+                }
+            });
+        }
+    }
+    expanded
+}
+
+pub fn slice(wrapper: &Wrapper) -> Result<TokenStream> {
+    let name = &wrapper.input.ident;
+    let vis = &wrapper.input.vis;
+    let self_field = wrapper.field(quote!(self));
+    let self_field_mut = wrapper.field_mut(quote!(self));
+    let from = wrapper.from;
+    let (impl_generics, ty_generics, where_clause) = wrapper.input.generics.split_for_impl();
+
+    let split_first_last = split_first_last(wrapper)?;
+
+    let ranges = [
+        quote!(::core::ops::Range<usize>),
+        quote!(::core::ops::RangeFrom<usize>),
+        quote!(::core::ops::RangeTo<usize>),
+        quote!(::core::ops::RangeFull),
+        quote!(::core::ops::RangeInclusive<usize>),
+        quote!(::core::ops::RangeToInclusive<usize>),
+    ];
+
+    Ok(quote! {
+        #(
+            impl #impl_generics ::core::ops::Index<#ranges> for #name #ty_generics #where_clause {
+                type Output = Self;
+
+                #[inline]
+                fn index(&self, index: #ranges) -> &Self {
+                    <Self as ::ref_cast::RefCast>::ref_cast(&#self_field[index])
+                }
+            }
+
+            impl #impl_generics ::core::ops::IndexMut<#ranges> for #name #ty_generics #where_clause {
+                #[inline]
+                fn index_mut(&mut self, index: #ranges) -> &mut Self {
+                    <Self as ::ref_cast::RefCast>::ref_cast_mut(&mut #self_field_mut[index])
+                }
+            }
+        )*
+
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #vis fn get<__I>(&self, index: __I) -> ::core::option::Option<&Self>
+            where
+                __I: ::core::slice::SliceIndex<#from, Output = #from>,
+            {
+                match #self_field.get(index) {
+                    ::core::option::Option::Some(slice) => ::core::option::Option::Some(<Self as ::ref_cast::RefCast>::ref_cast(slice)),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            #vis fn get_mut<__I>(&mut self, index: __I) -> ::core::option::Option<&mut Self>
+            where
+                __I: ::core::slice::SliceIndex<#from, Output = #from>,
+            {
+                match #self_field_mut.get_mut(index) {
+                    ::core::option::Option::Some(slice) => ::core::option::Option::Some(<Self as ::ref_cast::RefCast>::ref_cast_mut(slice)),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            #vis fn split_at(&self, mid: usize) -> (&Self, &Self) {
+                let (head, tail) = #self_field.split_at(mid);
+                (
+                    <Self as ::ref_cast::RefCast>::ref_cast(head),
+                    <Self as ::ref_cast::RefCast>::ref_cast(tail),
+                )
+            }
+
+            #vis fn split_at_mut(&mut self, mid: usize) -> (&mut Self, &mut Self) {
+                let (head, tail) = #self_field_mut.split_at_mut(mid);
+                (
+                    <Self as ::ref_cast::RefCast>::ref_cast_mut(head),
+                    <Self as ::ref_cast::RefCast>::ref_cast_mut(tail),
+                )
+            }
+
+            #split_first_last
+        }
+    })
+}
+
+// The split_first and split_last methods, whose element type depends on
+// whether the field is a slice or str.
+fn split_first_last(wrapper: &Wrapper) -> Result<TokenStream> {
+    let vis = &wrapper.input.vis;
+    let self_field = wrapper.field(quote!(self));
+    let self_field_mut = wrapper.field_mut(quote!(self));
+    let from = wrapper.from;
+
+    match from {
+        Type::Slice(slice) => {
+            let elem = &slice.elem;
+            Ok(quote! {
+                #vis fn split_first(&self) -> ::core::option::Option<(&#elem, &Self)> {
+                    match #self_field.split_first() {
+                        ::core::option::Option::Some((first, rest)) => ::core::option::Option::Some((first, <Self as ::ref_cast::RefCast>::ref_cast(rest))),
+                        ::core::option::Option::None => ::core::option::Option::None,
+                    }
+                }
+
+                #vis fn split_first_mut(&mut self) -> ::core::option::Option<(&mut #elem, &mut Self)> {
+                    match #self_field_mut.split_first_mut() {
+                        ::core::option::Option::Some((first, rest)) => ::core::option::Option::Some((first, <Self as ::ref_cast::RefCast>::ref_cast_mut(rest))),
+                        ::core::option::Option::None => ::core::option::Option::None,
+                    }
+                }
+
+                #vis fn split_last(&self) -> ::core::option::Option<(&#elem, &Self)> {
+                    match #self_field.split_last() {
+                        ::core::option::Option::Some((last, rest)) => ::core::option::Option::Some((last, <Self as ::ref_cast::RefCast>::ref_cast(rest))),
+                        ::core::option::Option::None => ::core::option::Option::None,
+                    }
+                }
+
+                #vis fn split_last_mut(&mut self) -> ::core::option::Option<(&mut #elem, &mut Self)> {
+                    match #self_field_mut.split_last_mut() {
+                        ::core::option::Option::Some((last, rest)) => ::core::option::Option::Some((last, <Self as ::ref_cast::RefCast>::ref_cast_mut(rest))),
+                        ::core::option::Option::None => ::core::option::Option::None,
+                    }
+                }
+            })
+        }
+        Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("str") => Ok(quote! {
+            #vis fn split_first(&self) -> ::core::option::Option<(char, &Self)> {
+                let mut chars = #self_field.chars();
+                match chars.next() {
+                    ::core::option::Option::Some(first) => ::core::option::Option::Some((first, <Self as ::ref_cast::RefCast>::ref_cast(chars.as_str()))),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            #vis fn split_last(&self) -> ::core::option::Option<(char, &Self)> {
+                match #self_field.char_indices().next_back() {
+                    ::core::option::Option::Some((i, last)) => ::core::option::Option::Some((last, <Self as ::ref_cast::RefCast>::ref_cast(&#self_field[..i]))),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+        }),
+        _ => Err(Error::new_spanned(
+            from,
+            "ref_cast(slice) requires a field of type [T] or str",
+        )),
+    }
+}
+
+// Equivalent to <#inner as RefCast>::From, but spelled out for slices and
+// arrays so that coherence does not consider it to overlap with #inner.
+pub fn source_of(inner: &Type) -> TokenStream {
+    match inner {
+        Type::Slice(ty) => {
+            let elem = source_of(&ty.elem);
+            quote!([#elem])
+        }
+        Type::Array(ty) => {
+            let elem = source_of(&ty.elem);
+            let len = &ty.len;
+            quote!([#elem; #len])
+        }
+        Type::Paren(ty) => source_of(&ty.elem),
+        _ => quote!(<#inner as ::ref_cast::RefCast>::From),
+    }
+}
+
+fn with_lifetime(generics: &Generics) -> (Lifetime, Generics) {
+    let lifetime = Lifetime::new("'__ref_cast", Span::call_site());
+    let mut generics = generics.clone();
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+    (lifetime, generics)
+}
+
+// Whether a type parameter occurs in `ty` other than inside a non-fundamental
+// type constructor, in which case the orphan rules do not allow `ty` as the
+// self type of an impl of a foreign trait.
+fn has_uncovered_type_param(ty: &Type, generics: &Generics) -> bool {
+    match ty {
+        Type::Path(ty) => {
+            if ty.qself.is_some() {
+                return generics.type_params().next().is_some();
+            }
+            if let Some(ident) = ty.path.get_ident() {
+                return generics.type_params().any(|param| param.ident == *ident);
+            }
+            if generics
+                .type_params()
+                .any(|param| ty.path.segments[0].ident == param.ident)
+            {
+                return true;
+            }
+            let segment = ty.path.segments.last().unwrap();
+            if segment.ident != "Box" && segment.ident != "Pin" {
+                return false;
+            }
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return false;
+            };
+            args.args.iter().any(|arg| match arg {
+                GenericArgument::Type(ty) => has_uncovered_type_param(ty, generics),
+                _ => false,
+            })
+        }
+        Type::Reference(ty) => has_uncovered_type_param(&ty.elem, generics),
+        Type::Group(ty) => has_uncovered_type_param(&ty.elem, generics),
+        Type::Paren(ty) => has_uncovered_type_param(&ty.elem, generics),
+        _ => false,
+    }
+}
+
+fn same_type(a: &Type, b: &Type) -> bool {
+    a.to_token_stream().to_string() == b.to_token_stream().to_string()
+}
+
+fn bounded(generics: &Generics, predicate: WherePredicate) -> WhereClause {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.push(predicate);
+    generics.where_clause.unwrap()
+}
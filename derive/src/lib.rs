@@ -6,14 +6,21 @@
 
 extern crate proc_macro;
 
+mod attr;
+mod impls;
+mod unsize;
+
+use crate::attr::{Attrs, Derive};
+use crate::impls::{Forward, Wrapper};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2, TokenTree};
 use quote::{quote, quote_spanned, ToTokens, TokenStreamExt as _};
-use syn::parse::{Nothing, ParseStream, Parser};
+use syn::parse::{ParseStream, Parser};
 use syn::punctuated::Punctuated;
 use syn::{
-    parenthesized, parse_macro_input, token, Abi, Attribute, Data, DeriveInput, Error, Expr, Field,
-    Generics, Path, Result, Token, Type, Visibility,
+    parenthesized, parse_macro_input, parse_quote, token, Abi, Attribute, Data, DeriveInput, Error,
+    Expr, Field, GenericArgument, Generics, Member, Path, PathArguments, Result, Token, Type,
+    TypePath, Visibility,
 };
 
 /// Derive the `RefCast` trait.
@@ -55,7 +62,349 @@ use syn::{
 ///     bytes: [u8],
 /// }
 /// ```
-#[proc_macro_derive(RefCast, attributes(trivial))]
+///
+/// Alternatively, mark the one field that references are converted from with
+/// `#[ref_cast(from)]`, and every other field is treated as trivial.
+///
+/// ```
+/// use ref_cast::RefCast;
+/// use std::marker::PhantomData;
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// pub struct Tagged<T, Tag> {
+///     tag: Marker<Tag>,
+///     #[ref_cast(from)]
+///     value: T,
+///     pin: Pin,
+/// }
+///
+/// type Marker<T> = PhantomData<T>;
+/// type Pin = std::marker::PhantomPinned;
+/// ```
+///
+/// # Enums
+///
+/// An enum with a single variant is supported as long as it is
+/// `#[repr(transparent)]`. The same rules as for structs apply to the fields of
+/// the variant.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// pub enum Message {
+///     Ping(Vec<u8>),
+/// }
+/// ```
+///
+/// # Nested wrappers
+///
+/// Every `derive(RefCast)` also implements [`RefCastFrom`] for the type of its
+/// field. Use `#[ref_cast(via = Inner)]` on a wrapper whose field is itself a
+/// `RefCast` type `Inner` to additionally implement `RefCastFrom` for the
+/// source type of `Inner`, skipping the intermediate cast. Repeating `via`
+/// with the next type inward continues the chain.
+///
+/// [`RefCastFrom`]: ./trait.RefCastFrom.html
+///
+/// ```
+/// use ref_cast::{RefCast, RefCastFrom};
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// pub struct Raw([u8]);
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(via = Raw)]
+/// #[repr(transparent)]
+/// pub struct Utf8(Raw);
+///
+/// // &Utf8 -> &Raw -> &[u8]
+/// #[derive(RefCast)]
+/// #[ref_cast(via = Utf8, via = Raw)]
+/// #[repr(transparent)]
+/// pub struct Ident(Utf8);
+///
+/// let ident = Ident::ref_cast_from(&b"main"[..]);
+/// ```
+///
+/// Since slices of `RefCast` types are themselves `RefCast`, a wrapper around
+/// a slice of wrappers can be cast from a slice of the innermost type with
+/// `#[ref_cast(via = [Element])]`.
+///
+/// ```
+/// use ref_cast::{RefCast, RefCastFrom};
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// pub struct Component(u32);
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(via = [Component])]
+/// #[repr(transparent)]
+/// pub struct Path([Component]);
+///
+/// let path = Path::ref_cast_from(&[1, 2, 3][..]);
+/// ```
+///
+/// Use `#[ref_cast(cast_from(...))]` to implement `RefCastFrom` for each of the
+/// listed types, going through their `DerefMut` impl that targets the field
+/// type. This works for transparent wrappers from the standard library like
+/// `ManuallyDrop<T>` as well as for owned types like `String`.
+///
+/// ```
+/// use ref_cast::RefCast;
+/// use std::mem::ManuallyDrop;
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(cast_from(ManuallyDrop<str>, String))]
+/// #[repr(transparent)]
+/// pub struct Key(str);
+///
+/// let string = String::from("key");
+/// let key: &Key = ref_cast::cast(&string);
+/// ```
+///
+/// # Borrow
+///
+/// Use `#[ref_cast(borrow(...))]` to generate `Borrow<Self>` impls for each of
+/// the listed types, going through their `Borrow` impl for the field type. This
+/// also generates `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` impls on
+/// the wrapper that delegate to the field, which is what the `Borrow` contract
+/// requires for lookups in a map keyed by one of the listed types.
+///
+/// ```
+/// use ref_cast::RefCast;
+/// use std::collections::HashMap;
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(borrow(String))]
+/// #[repr(transparent)]
+/// pub struct Name(str);
+///
+/// let mut map = HashMap::new();
+/// map.insert("main".to_owned(), 0);
+/// assert_eq!(map[Name::ref_cast("main")], 0);
+/// ```
+///
+/// # Forwarding
+///
+/// Use `#[ref_cast(forward(...))]` to generate impls of the listed traits that
+/// forward to the field that references are converted from, ignoring trivial
+/// fields. The supported traits are `Debug`, `Display`, `PartialEq`, `Eq`,
+/// `PartialOrd`, `Ord` and `Hash`.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(forward(Debug, Display, PartialEq))]
+/// #[repr(transparent)]
+/// pub struct Name(str);
+///
+/// let name = Name::ref_cast("main");
+/// assert_eq!(format!("{:?} {}", name, name), "\"main\" main");
+/// ```
+///
+/// # Comparisons
+///
+/// Use `#[ref_cast(compare(PartialEq, PartialOrd))]` to generate comparisons
+/// between the wrapper and the field type, in both directions and with `&From`
+/// as the other operand, the same way that the standard library compares
+/// `String` with `str` and `&str`. `PartialOrd` implies `PartialEq`, which it
+/// requires. Comparisons with the field type on the left are not generated if
+/// the field type is like `T`, `&T` or `Box<T>` for a type parameter `T`, as
+/// coherence does not allow them.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(compare(PartialEq, PartialOrd))]
+/// #[repr(transparent)]
+/// pub struct Name(str);
+///
+/// let name = Name::ref_cast("main");
+/// assert!(name == "main");
+/// assert!(*name == "main");
+/// assert!("lib" < *name);
+/// ```
+///
+/// # Conversions
+///
+/// The following options generate conversion impls in terms of `RefCast`:
+///
+/// - `#[ref_cast(as_ref)]` &mdash; `AsRef<From>` for the wrapper, and
+///   `AsRef<Wrapper>` for the field type unless the field type is like `T`,
+///   `&T` or `Box<T>` for a type parameter `T`, which coherence does not
+///   allow,
+/// - `#[ref_cast(from_ref)]` &mdash; `From<&From>` for `&Wrapper` and
+///   `From<&mut From>` for `&mut Wrapper`,
+/// - `#[ref_cast(deref)]` &mdash; `Deref<Target = From>` for the wrapper.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(as_ref, from_ref, deref)]
+/// #[repr(transparent)]
+/// pub struct Name(str);
+///
+/// fn len(name: &(impl AsRef<Name> + ?Sized)) -> usize {
+///     name.as_ref().len()  // through Deref
+/// }
+///
+/// let name: &Name = "main".into();
+/// assert_eq!(len(&name.0), 4);
+/// ```
+///
+/// For a wrapper around a sized type, `#[ref_cast(wrap)]` generates inherent
+/// `wrap(From) -> Self` and `into_inner(self) -> From` methods that convert by
+/// value. Trivial fields are filled in with their zero-sized value, so a
+/// wrapper with private `PhantomData` markers can be constructed outside of
+/// its module.
+///
+/// ```
+/// mod units {
+///     use ref_cast::RefCast;
+///     use std::marker::PhantomData;
+///
+///     pub struct Meters;
+///
+///     #[derive(RefCast)]
+///     #[ref_cast(wrap)]
+///     #[repr(transparent)]
+///     pub struct Length<Unit> {
+///         value: f64,
+///         unit: PhantomData<Unit>,
+///     }
+/// }
+///
+/// use units::{Length, Meters};
+///
+/// let length = Length::<Meters>::wrap(1.5);
+/// assert_eq!(length.into_inner(), 1.5);
+/// ```
+///
+/// # Pinning
+///
+/// `#[ref_cast(pin)]` treats the field as structurally pinned and generates
+/// inherent `project(self: Pin<&mut Self>) -> Pin<&mut From>` and
+/// `pin_ref_cast(Pin<&mut From>) -> Pin<&mut Self>` methods, which is all a
+/// newtype around a future or stream needs from `pin-project`. To keep the
+/// projection sound, the wrapper must not implement `Drop`, and it is only
+/// `Unpin` when all of its fields are, which rules out a handwritten `Unpin`
+/// impl.
+///
+/// ```
+/// use ref_cast::RefCast;
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(pin)]
+/// #[repr(transparent)]
+/// pub struct Instrumented<F>(F);
+///
+/// impl<F: Future> Future for Instrumented<F> {
+///     type Output = F::Output;
+///
+///     fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+///         eprintln!("polling");
+///         self.project().poll(cx)
+///     }
+/// }
+/// ```
+///
+/// # Slicing
+///
+/// For a wrapper around `[T]` or `str`, use `#[ref_cast(slice)]` to generate
+/// slicing operations that return the wrapper type instead of the field type:
+/// `Index` and `IndexMut` for every kind of range, as well as inherent `get`,
+/// `split_at`, `split_first` and `split_last` methods with the same visibility
+/// as the wrapper. This is opt-in because not every wrapper's invariants hold
+/// for arbitrary subslices.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(slice, forward(Debug, PartialEq))]
+/// #[repr(transparent)]
+/// pub struct Frame([u8]);
+///
+/// let frame = Frame::ref_cast(b"\x02okay");
+/// let (len, rest): (&u8, &Frame) = frame.split_first().unwrap();
+/// let payload: &Frame = &rest[..*len as usize];
+/// assert_eq!(payload.get(1..), Some(Frame::ref_cast(b"k")));
+/// ```
+///
+/// # Unsizing
+///
+/// For a wrapper whose field is a `?Sized` type parameter, use
+/// `#[ref_cast(unsize)]` to generate inherent `unsize` and `unsize_mut`
+/// methods that turn `&Wrapper<[T; N]>` into `&Wrapper<[T]>`, which the
+/// compiler does not do implicitly for user-defined types.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(unsize)]
+/// #[repr(transparent)]
+/// pub struct Buffer<T: ?Sized>(T);
+///
+/// fn checksum(buffer: &Buffer<[u8]>) -> u8 {
+///     buffer.0.iter().fold(0, |sum, byte| sum ^ byte)
+/// }
+///
+/// let buffer: &Buffer<[u8; 16]> = Buffer::ref_cast(&[0; 16]);
+/// checksum(buffer.unsize());
+/// ```
+///
+/// Similarly `#[ref_cast(unsize = dyn Trait)]` generates `as_dyn` and
+/// `as_dyn_mut`, which turn `&Wrapper<T>` into `&Wrapper<dyn Trait>` for any
+/// `T: Trait`. A `&dyn Trait` obtained elsewhere, for example from a
+/// `Box<dyn Trait>`, can be cast to `&Wrapper<dyn Trait>` directly through the
+/// `RefCast` impl.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// pub trait Handler {
+///     fn handle(&self, request: &str);
+/// }
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(unsize = dyn Handler)]
+/// #[repr(transparent)]
+/// pub struct Instrumented<H: ?Sized>(H);
+///
+/// impl Instrumented<dyn Handler> {
+///     pub fn handle(&self, request: &str) {
+///         eprintln!("handling {}", request);
+///         self.0.handle(request);
+///     }
+/// }
+///
+/// struct Noop;
+///
+/// impl Handler for Noop {
+///     fn handle(&self, _request: &str) {}
+/// }
+///
+/// let handler = Instrumented(Noop);
+/// handler.as_dyn().handle("/");
+///
+/// let plugins: Vec<Box<dyn Handler>> = vec![Box::new(Noop)];
+/// for plugin in &plugins {
+///     Instrumented::ref_cast(&**plugin).handle("/");
+/// }
+/// ```
+#[proc_macro_derive(RefCast, attributes(ref_cast, trivial))]
 pub fn derive_ref_cast(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     expand_ref_cast(&input)
@@ -69,7 +418,21 @@ pub fn derive_ref_cast(input: TokenStream) -> TokenStream {
 /// Please refer to the documentation of
 /// [`#[ref_cast_custom]`][macro@ref_cast_custom] where these two macros are
 /// documented together.
-#[proc_macro_derive(RefCastCustom, attributes(trivial))]
+///
+/// `#[ref_cast(via = Inner)]` works with this derive too, making
+/// `#[ref_cast_custom]` functions able to cast from the source type of the
+/// `RefCast` type `Inner`, including from `[T]` for a field of type `[Inner]`.
+///
+/// Unlike `derive(RefCast)`, this derive does not implement `RefCastFrom`
+/// unless asked to with `#[ref_cast(cast_from(...))]`, in which case it is
+/// implemented for exactly the listed types, which may include the field type.
+/// Those impls are public and safe to call from any crate, so listing a type
+/// in `cast_from` gives up the restriction that only this crate can cast to the
+/// type from it.
+///
+/// Generic code can bound on the relationship this derive establishes through
+/// the public `RefCastCustomFrom` marker trait.
+#[proc_macro_derive(RefCastCustom, attributes(ref_cast, trivial))]
 pub fn derive_ref_cast_custom(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     expand_ref_cast_custom(&input)
@@ -127,6 +490,122 @@ pub fn derive_ref_cast_custom(input: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// Rather than declaring near-duplicate functions for each kind of pointer,
+/// a function from `&T` to `&U` can name the other functions to generate
+/// alongside it, which share its documentation and visibility:
+///
+/// - `mut = name` &mdash; from `&mut T` to `&mut U`,
+/// - `boxed = name` &mdash; from `Box<T>` to `Box<U>`,
+/// - `rc = name` &mdash; from `Rc<T>` to `Rc<U>`,
+/// - `arc = name` &mdash; from `Arc<T>` to `Arc<U>`.
+///
+/// The smart pointer casts require ref-cast's `alloc` feature. The generated
+/// functions other than the `&T` one are never `const fn`, even if the `&T`
+/// function is.
+///
+/// A validated type can have its check performed by the generated function.
+/// With `check = path::to::predicate`, the function returns `Option<&U>`, and
+/// casts if `predicate(&T) -> bool` returns true. If the predicate instead
+/// returns `Result<(), E>`, the function returns `Result<&U, E>`. This works
+/// in a `const fn` when the predicate is a `const fn`. The check combines with
+/// `mut`, but not with `boxed`, `rc` or `arc`, which would drop the argument
+/// whenever the check fails.
+///
+/// ```rust
+/// use ref_cast::{ref_cast_custom, RefCastCustom};
+///
+/// #[derive(RefCastCustom)]
+/// #[repr(transparent)]
+/// pub struct Ascii([u8]);
+///
+/// impl Ascii {
+///     const fn is_valid(bytes: &[u8]) -> bool {
+///         bytes.is_ascii()
+///     }
+///
+///     #[ref_cast_custom(check = Self::is_valid)]
+///     pub const fn new(bytes: &[u8]) -> Option<&Self>;
+/// }
+///
+/// const HELLO: Option<&Ascii> = Ascii::new(b"hello");
+/// assert!(HELLO.is_some());
+/// assert!(Ascii::new(b"\xF0\x9F\xA6\x80").is_none());
+/// ```
+///
+/// ```rust
+/// # use ref_cast::{ref_cast_custom, RefCastCustom};
+/// #
+/// # #[derive(RefCastCustom)]
+/// # #[repr(transparent)]
+/// # pub struct Frame([u8]);
+/// #
+/// impl Frame {
+///     /// Views bytes as a frame.
+///     #[ref_cast_custom(mut = new_mut)]
+///     pub(crate) const fn new(bytes: &[u8]) -> &Self;
+/// }
+/// ```
+///
+/// By default only the crate that defines the type with `derive(RefCastCustom)`
+/// may write `#[ref_cast_custom]` functions for it. For a type that is split
+/// across several crates, `#[ref_cast(capability = Token)]` opts in to casts
+/// from anywhere that can construct `Token` using the struct expression
+/// `Token {}`. The token is typically a public struct with no fields, whose
+/// uses are easy to audit.
+///
+/// ```rust
+/// // in the -core crate
+/// # mod frame_core {
+/// use ref_cast::RefCastCustom;
+///
+/// #[doc(hidden)]
+/// pub struct ExtCapability {}
+///
+/// #[derive(RefCastCustom)]
+/// #[ref_cast(capability = ExtCapability)]
+/// #[repr(transparent)]
+/// pub struct Frame([u8]);
+/// # }
+///
+/// // in the -ext crate
+/// # mod frame_ext {
+/// # use crate::frame_core::Frame;
+/// use ref_cast::ref_cast_custom;
+///
+/// #[ref_cast_custom]
+/// pub fn frame(bytes: &[u8]) -> &Frame;
+/// # }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// In the other direction, `#[ref_cast(scope = pub(self))]` restricts
+/// `#[ref_cast_custom]` functions to the module that defines the type, and
+/// `#[ref_cast(scope = pub(in path))]` to the given module and its descendants.
+/// This way a validated wrapper's invariants only need to be upheld by the
+/// code in that module. Neither `scope` nor `capability` can be combined with
+/// `#[ref_cast(cast_from(...))]`, whose impls are callable from anywhere.
+///
+/// ```rust
+/// mod ascii {
+///     use ref_cast::{ref_cast_custom, RefCastCustom};
+///
+///     #[derive(RefCastCustom)]
+///     #[ref_cast(scope = pub(self))]
+///     #[repr(transparent)]
+///     pub struct Ascii(str);
+///
+///     impl Ascii {
+///         #[ref_cast_custom]  // allowed only in this module
+///         fn new_unchecked(s: &str) -> &Self;
+///
+///         pub fn new(s: &str) -> Option<&Self> {
+///             s.is_ascii().then(|| Self::new_unchecked(s))
+///         }
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn ref_cast_custom(args: TokenStream, input: TokenStream) -> TokenStream {
     let input = TokenStream2::from(input);
@@ -160,9 +639,9 @@ pub fn ref_cast_custom(args: TokenStream, input: TokenStream) -> TokenStream {
         generics.where_clause = input.parse()?;
         let semi_token: Token![;] = input.parse()?;
 
-        let _: Nothing = syn::parse::<Nothing>(args)?;
+        let custom_args = attr::custom_args(TokenStream2::from(args))?;
 
-        Ok(Function {
+        let function = Function {
             attrs,
             vis,
             constness,
@@ -179,11 +658,94 @@ pub fn ref_cast_custom(args: TokenStream, input: TokenStream) -> TokenStream {
             arrow_token,
             to_type,
             semi_token,
-        })
+        };
+        expand_function_family(function, custom_args)
     })
     .parse2(input.clone())
     {
-        Ok(function) => expand_function_body(function),
+        Ok(expanded) => expanded,
+        Err(parse_error) => {
+            let compile_error = parse_error.to_compile_error();
+            quote!(#compile_error #input)
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+/// Cast a reference at the call site, without declaring a named casting
+/// function.
+///
+/// `ref_cast!(expr as &Type)` or `ref_cast!(expr as &mut Type)` casts the
+/// reference `expr` to a type that has `derive(RefCastCustom)`. The source
+/// type is inferred from `expr`, and is checked to be the type of the single
+/// non-trivial field of `Type`, or a source type listed by `#[ref_cast(via =
+/// ...)]`. The same restrictions as for a [`#[ref_cast_custom]`][macro@ref_cast_custom]
+/// function apply: the lifetime of the result is that of the argument, and the
+/// macro can only be used in the crate, and module if `#[ref_cast(scope =
+/// ...)]` is given, that is allowed to define casting functions for `Type`.
+///
+/// This is meant for places where a named function would be overkill, such as
+/// tests and one-off adapters.
+///
+/// ```rust
+/// use ref_cast::{ref_cast, RefCastCustom};
+///
+/// #[derive(RefCastCustom)]
+/// #[repr(transparent)]
+/// pub struct Frame([u8]);
+///
+/// let bytes = vec![0u8; 16];
+/// let frame = ref_cast!(&bytes[..] as &Frame);
+///
+/// const EMPTY: &Frame = ref_cast!(b"" as &Frame);
+/// ```
+#[proc_macro]
+pub fn ref_cast(input: TokenStream) -> TokenStream {
+    expand_cast_expr(TokenStream2::from(input))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Attribute that applies `#[repr(transparent)]` and `derive(RefCast)` to a
+/// struct, so that the two cannot get out of sync.
+///
+/// ```rust
+/// #[ref_cast::transparent]
+/// #[ref_cast(as_ref)]
+/// pub struct Name(str);
+///
+/// #[ref_cast::transparent(RefCastCustom)]
+/// pub struct Frame([u8]);
+///
+/// impl Frame {
+///     #[ref_cast::ref_cast_custom]
+///     pub fn new(bytes: &[u8]) -> &Self;
+/// }
+/// ```
+///
+/// With an argument of `RefCastCustom`, the attribute applies
+/// `derive(RefCastCustom)` instead. If the struct already has a `repr`
+/// attribute, such as `#[repr(C)]`, no repr is added and the derive checks the
+/// existing one as usual.
+#[proc_macro_attribute]
+pub fn transparent(args: TokenStream, input: TokenStream) -> TokenStream {
+    let input = TokenStream2::from(input);
+    let expanded = match (|| {
+        let derive = syn::parse::<Option<Ident>>(args)?;
+        let derive = match derive {
+            None => Ident::new("RefCast", Span::call_site()),
+            Some(derive) if derive == "RefCast" || derive == "RefCastCustom" => derive,
+            Some(other) => {
+                return Err(Error::new(
+                    other.span(),
+                    "expected RefCast or RefCastCustom",
+                ));
+            }
+        };
+        let item: DeriveInput = syn::parse2(input.clone())?;
+        Ok(expand_transparent(&derive, &item))
+    })() {
+        Ok(expanded) => expanded,
         Err(parse_error) => {
             let compile_error = parse_error.to_compile_error();
             quote!(#compile_error #input)
@@ -204,6 +766,7 @@ impl ToTokens for private {
     }
 }
 
+#[derive(Clone)]
 struct Function {
     attrs: Vec<Attribute>,
     vis: Visibility,
@@ -230,9 +793,10 @@ fn expand_ref_cast(input: &DeriveInput) -> Result<TokenStream2> {
     let name_str = name.to_string();
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let fields = fields(input)?;
-    let from = only_field_ty(fields)?;
+    let (variant, fields) = fields(input)?;
+    let (member, from) = only_field(fields)?;
     let trivial = trivial_fields(fields)?;
+    let attrs = attr::get(input, Derive::RefCast)?;
     let private2 = private;
 
     let assert_trivial_fields = if !trivial.is_empty() {
@@ -247,6 +811,47 @@ fn expand_ref_cast(input: &DeriveInput) -> Result<TokenStream2> {
         None
     };
 
+    let assert_layout = quote! {
+        #[cfg(debug_assertions)]
+        {
+            #[allow(unused_imports)]
+            use ::ref_cast::#private::LayoutUnsized;
+            ::ref_cast::#private::assert_layout::<Self, Self::From>(
+                #name_str,
+                ::ref_cast::#private::Layout::<Self>::SIZE,
+                ::ref_cast::#private::Layout::<Self::From>::SIZE,
+                ::ref_cast::#private::Layout::<Self>::ALIGN,
+                ::ref_cast::#private::Layout::<Self::From>::ALIGN,
+            );
+            let _from: &Self::From = &*_from;
+            let _outer = unsafe { &*(_from as *const Self::From as *const Self) };
+            ::ref_cast::#private::assert_layout_of_val::<Self, Self::From>(
+                #name_str,
+                _outer,
+                _from,
+            );
+        }
+    };
+
+    let wrapper = Wrapper {
+        input,
+        variant,
+        member,
+        from,
+    };
+
+    let option_impls = expand_options(&wrapper, &attrs, fields, &trivial)?;
+
+    // A packed wrapper can have a smaller alignment than its field, so it does
+    // not get the impl that slices, arrays and Vec casts rely on.
+    let transparent_impl = if is_packed(input) {
+        None
+    } else {
+        Some(quote! {
+            unsafe impl #impl_generics ::ref_cast::#private::Transparent<#from> for #name #ty_generics #where_clause {}
+        })
+    };
+
     Ok(quote! {
         impl #impl_generics ::ref_cast::RefCast for #name #ty_generics #where_clause {
             type From = #from;
@@ -254,18 +859,7 @@ fn expand_ref_cast(input: &DeriveInput) -> Result<TokenStream2> {
             #[inline]
             fn ref_cast(_from: &Self::From) -> &Self {
                 #assert_trivial_fields
-                #[cfg(debug_assertions)]
-                {
-                    #[allow(unused_imports)]
-                    use ::ref_cast::#private::LayoutUnsized;
-                    ::ref_cast::#private::assert_layout::<Self, Self::From>(
-                        #name_str,
-                        ::ref_cast::#private::Layout::<Self>::SIZE,
-                        ::ref_cast::#private::Layout::<Self::From>::SIZE,
-                        ::ref_cast::#private::Layout::<Self>::ALIGN,
-                        ::ref_cast::#private::Layout::<Self::From>::ALIGN,
-                    );
-                }
+                #assert_layout
                 unsafe {
                     &*(_from as *const Self::From as *const Self)
                 }
@@ -273,23 +867,72 @@ fn expand_ref_cast(input: &DeriveInput) -> Result<TokenStream2> {
 
             #[inline]
             fn ref_cast_mut(_from: &mut Self::From) -> &mut Self {
-                #[cfg(debug_assertions)]
-                {
-                    #[allow(unused_imports)]
-                    use ::ref_cast::#private::LayoutUnsized;
-                    ::ref_cast::#private::assert_layout::<Self, Self::From>(
-                        #name_str,
-                        ::ref_cast::#private::Layout::<Self>::SIZE,
-                        ::ref_cast::#private::Layout::<Self::From>::SIZE,
-                        ::ref_cast::#private::Layout::<Self>::ALIGN,
-                        ::ref_cast::#private::Layout::<Self::From>::ALIGN,
-                    );
-                }
+                #assert_layout
                 unsafe {
                     &mut *(_from as *mut Self::From as *mut Self)
                 }
             }
         }
+
+        #transparent_impl
+
+        #option_impls
+    })
+}
+
+// Impls requested through #[ref_cast(...)] options on derive(RefCast).
+fn expand_options(
+    wrapper: &Wrapper,
+    attrs: &Attrs,
+    fields: &Punctuated<Field, Token![,]>,
+    trivial: &[&Type],
+) -> Result<TokenStream2> {
+    let mut forward = attrs.forward.clone();
+    if !attrs.borrow.is_empty() {
+        for trait_ in Forward::BORROW {
+            if !forward.contains(&trait_) {
+                forward.push(trait_);
+            }
+        }
+    }
+
+    let ref_cast_from_impl = impls::ref_cast_from(wrapper);
+    let via_impls = impls::via(wrapper, &attrs.via);
+    let cast_from_impls = impls::cast_from(wrapper, &attrs.cast_from);
+    let borrow_impls = impls::borrow(wrapper, &attrs.borrow);
+    let forward_impls = impls::forward(wrapper, &forward);
+    let compare_impls = impls::compare(wrapper, &attrs.compare);
+    let as_ref_impls = attrs.as_ref.map(|_| impls::as_ref(wrapper));
+    let from_impls = attrs.from_ref.map(|_| impls::from(wrapper));
+    let deref_impl = attrs.deref.map(|_| impls::deref(wrapper));
+    let wrap_impl = attrs.wrap.map(|_| impls::wrap(wrapper, fields));
+    let pin_impls = attrs.pin.map(|_| impls::pin(wrapper, trivial));
+    let slice_impls = attrs.slice.map(|_| impls::slice(wrapper)).transpose()?;
+    let unsize_impl = attrs
+        .unsize
+        .map(|_| unsize::array_to_slice(wrapper))
+        .transpose()?;
+    let unsize_dyn_impl = attrs
+        .unsize_dyn
+        .as_ref()
+        .map(|dyn_ty| unsize::to_dyn(wrapper, dyn_ty))
+        .transpose()?;
+
+    Ok(quote! {
+        #ref_cast_from_impl
+        #via_impls
+        #cast_from_impls
+        #borrow_impls
+        #forward_impls
+        #compare_impls
+        #as_ref_impls
+        #from_impls
+        #deref_impl
+        #wrap_impl
+        #pin_impls
+        #slice_impls
+        #unsize_impl
+        #unsize_dyn_impl
     })
 }
 
@@ -300,11 +943,14 @@ fn expand_ref_cast_custom(input: &DeriveInput) -> Result<TokenStream2> {
     let name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let fields = fields(input)?;
-    let from = only_field_ty(fields)?;
+    let (variant, fields) = fields(input)?;
+    let (member, from) = only_field(fields)?;
     let trivial = trivial_fields(fields)?;
+    let attrs = attr::get(input, Derive::RefCastCustom)?;
     let private2 = private;
 
+    check_cast_from(&attrs)?;
+
     let assert_trivial_fields = if !trivial.is_empty() {
         Some(quote! {
             fn __static_assert() {
@@ -319,20 +965,311 @@ fn expand_ref_cast_custom(input: &DeriveInput) -> Result<TokenStream2> {
         None
     };
 
+    let wrapper = Wrapper {
+        input,
+        variant,
+        member,
+        from,
+    };
+
+    let ref_cast_from_impls = impls::ref_cast_from_custom(&wrapper, &trivial, &attrs.cast_from);
+
+    // Only code that can construct the CurrentCrate type with a struct
+    // expression can define #[ref_cast_custom] functions. By default that is a
+    // #[non_exhaustive] struct, which restricts them to the current crate.
+    let (current_crate, current_crate_decl) = match &attrs.capability {
+        Some(capability) => (quote!(#capability), None),
+        None => (
+            quote!(RefCastCurrentCrate),
+            Some(quote! {
+                #[non_exhaustive]
+                #vis struct RefCastCurrentCrate {}
+            }),
+        ),
+    };
+
+    // The generated #[ref_cast_custom] function reads the ref_cast_scope field
+    // of the CurrentModule type, so field privacy restricts where it can be.
+    let (current_module, current_module_decl) = match &attrs.scope {
+        Some(scope) => (
+            quote!(RefCastCurrentModule),
+            Some(quote! {
+                #vis struct RefCastCurrentModule {
+                    #scope ref_cast_scope: (),
+                }
+            }),
+        ),
+        None => (quote!(::ref_cast::#private::AnyModule), None),
+    };
+
+    // Casts that reallocate or free through the target type, like the Box,
+    // Rc and Arc casts, require the same alignment, which a packed wrapper may
+    // not have.
+    let alignment = if is_packed(input) {
+        quote!(::ref_cast::#private::MaybeLowerAlign)
+    } else {
+        quote!(::ref_cast::#private::SameAlign)
+    };
+
+    let via_impls = attrs.via.iter().map(|inner| {
+        let mut generics = input.generics.clone();
+        let where_clause = generics.make_where_clause();
+        where_clause.predicates.push(parse_quote! {
+            #inner: ::ref_cast::RefCast + ::ref_cast::#private::Transparent<<#inner as ::ref_cast::RefCast>::From>
+        });
+        where_clause.predicates.push(parse_quote! {
+            #name #ty_generics: ::ref_cast::#private::RefCastCustom<#inner>
+        });
+        let source = impls::source_of(inner);
+        quote! {
+            unsafe impl #impl_generics ::ref_cast::#private::RefCastCustom<#source> for #name #ty_generics #where_clause {
+                type CurrentCrate = #current_crate;
+                type CurrentModule = #current_module;
+                type Alignment = #alignment;
+            }
+        }
+    });
+
     Ok(quote! {
+        #ref_cast_from_impls
+
         const _: () = {
-            #[non_exhaustive]
-            #vis struct RefCastCurrentCrate {}
+            #current_crate_decl
+            #current_module_decl
 
             unsafe impl #impl_generics ::ref_cast::#private::RefCastCustom<#from> for #name #ty_generics #where_clause {
-                type CurrentCrate = RefCastCurrentCrate;
+                type CurrentCrate = #current_crate;
+                type CurrentModule = #current_module;
+                type Alignment = #alignment;
                 #assert_trivial_fields
             }
+
+            impl #impl_generics ::ref_cast::#private::RefCastTarget for #name #ty_generics #where_clause {
+                type CurrentCrate = #current_crate;
+                type CurrentModule = #current_module;
+            }
+
+            #(#via_impls)*
         };
     })
 }
 
-fn expand_function_body(function: Function) -> TokenStream2 {
+// RefCastFrom impls can be called from anywhere, which would bypass the
+// restriction that capability or scope is meant to put in place.
+fn check_cast_from(attrs: &Attrs) -> Result<()> {
+    let Some(source) = attrs.cast_from.first() else {
+        return Ok(());
+    };
+    let restriction = if attrs.scope.is_some() {
+        "scope"
+    } else if attrs.capability.is_some() {
+        "capability"
+    } else {
+        return Ok(());
+    };
+    Err(Error::new_spanned(
+        source,
+        format!(
+            "ref_cast(cast_from(...)) cannot be combined with ref_cast({restriction} = ...), because RefCastFrom impls are callable from any module and crate",
+        ),
+    ))
+}
+
+// How the generated function body converts the argument.
+enum Cast {
+    Transmute,
+    // Through the into_raw and from_raw functions of a smart pointer type.
+    Raw {
+        path: TokenStream2,
+        ptr: TokenStream2,
+        target: TokenStream2,
+    },
+}
+
+// A #[ref_cast_custom(check = ...)] function returning Option<&U> or
+// Result<&U, E>, which casts only if the check passes.
+struct Check {
+    expr: Expr,
+    output: TypePath,
+    fallible: Fallible,
+}
+
+enum Fallible {
+    Option,
+    Result,
+}
+
+impl Check {
+    fn new(expr: Expr, function: &mut Function) -> Result<Self> {
+        let error = || {
+            Error::new_spanned(
+                &function.to_type,
+                "ref_cast_custom(check = ...) requires a return type of Option<&U> or Result<&U, E>",
+            )
+        };
+        let Type::Path(output) = &function.to_type else {
+            return Err(error());
+        };
+        let segment = output.path.segments.last().unwrap();
+        let fallible = if segment.ident == "Option" {
+            Fallible::Option
+        } else if segment.ident == "Result" {
+            Fallible::Result
+        } else {
+            return Err(error());
+        };
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return Err(error());
+        };
+        let Some(GenericArgument::Type(inner)) = args.args.first() else {
+            return Err(error());
+        };
+        let inner = inner.clone();
+        let output = output.clone();
+        function.to_type = inner;
+        Ok(Check {
+            expr,
+            output,
+            fallible,
+        })
+    }
+
+    // Runs the check on the argument, then `checked_cast` which binds `to`.
+    fn body(&self, arg: &Ident, checked_cast: &TokenStream2) -> TokenStream2 {
+        let expr = &self.expr;
+        let call = if let Expr::Path(_) = expr {
+            quote!(#expr(&*#arg))
+        } else {
+            quote!((#expr)(&*#arg))
+        };
+        match self.fallible {
+            Fallible::Option => quote! {
+                if #call {
+                    #checked_cast
+                    ::core::option::Option::Some(to)
+                } else {
+                    ::core::option::Option::None
+                }
+            },
+            Fallible::Result => quote! {
+                match #call {
+                    ::core::result::Result::Ok(()) => {
+                        #checked_cast
+                        ::core::result::Result::Ok(to)
+                    }
+                    ::core::result::Result::Err(error) => ::core::result::Result::Err(error),
+                }
+            },
+        }
+    }
+
+    // The return type, given the type of the reference being returned.
+    fn output(&self, to_type: &Type) -> Type {
+        let mut output = self.output.clone();
+        let segment = output.path.segments.last_mut().unwrap();
+        if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+            args.args[0] = GenericArgument::Type(to_type.clone());
+        }
+        Type::Path(output)
+    }
+}
+
+fn expand_function_family(
+    mut function: Function,
+    mut custom_args: attr::CustomArgs,
+) -> Result<TokenStream2> {
+    // The argument would be dropped whenever the check fails.
+    if custom_args.check.is_some() {
+        let smart_pointer = [&custom_args.box_fn, &custom_args.rc_fn, &custom_args.arc_fn];
+        if let Some(ident) = smart_pointer.into_iter().flatten().next() {
+            return Err(Error::new_spanned(
+                ident,
+                "ref_cast_custom(check = ...) cannot be combined with boxed, rc or arc",
+            ));
+        }
+    }
+
+    let check = match custom_args.check.take() {
+        Some(expr) => Some(Check::new(expr, &mut function)?),
+        None => None,
+    };
+    let check = check.as_ref();
+
+    if !custom_args.has_family() {
+        return Ok(expand_function_body(function, Cast::Transmute, check));
+    }
+
+    let (Type::Reference(from_ref), Type::Reference(to_ref)) =
+        (&function.from_type, &function.to_type)
+    else {
+        return Err(Error::new_spanned(
+            &function.from_type,
+            "ref_cast_custom with arguments requires a function from &T to &U",
+        ));
+    };
+    if let Some(mutability) = from_ref.mutability.or(to_ref.mutability) {
+        return Err(Error::new_spanned(
+            mutability,
+            "ref_cast_custom with arguments requires a function from &T to &U",
+        ));
+    }
+    let from_elem = &from_ref.elem;
+    let to_elem = &to_ref.elem;
+
+    let mut expanded = expand_function_body(function.clone(), Cast::Transmute, check);
+
+    if let Some(ident) = custom_args.mut_fn {
+        let mut from_ref = from_ref.clone();
+        let mut to_ref = to_ref.clone();
+        from_ref.mutability = Some(Token![mut](from_ref.and_token.span));
+        to_ref.mutability = Some(Token![mut](to_ref.and_token.span));
+        expanded.extend(expand_function_body(
+            Function {
+                // &mut in a const fn requires a newer compiler than ref-cast's
+                // minimum supported Rust version.
+                constness: None,
+                ident,
+                from_type: Type::Reference(from_ref),
+                to_type: Type::Reference(to_ref),
+                ..function.clone()
+            },
+            Cast::Transmute,
+            check,
+        ));
+    }
+
+    let smart_pointers = [
+        (custom_args.box_fn, quote!(Box), quote!(*mut)),
+        (custom_args.rc_fn, quote!(Rc), quote!(*const)),
+        (custom_args.arc_fn, quote!(Arc), quote!(*const)),
+    ];
+    for (ident, pointer, ptr) in smart_pointers {
+        let Some(ident) = ident else {
+            continue;
+        };
+        let path = quote!(::ref_cast::#private::#pointer);
+        expanded.extend(expand_function_body(
+            Function {
+                // from_raw and into_raw are not const.
+                constness: None,
+                ident,
+                from_type: parse_quote!(#path<#from_elem>),
+                to_type: parse_quote!(#path<#to_elem>),
+                ..function.clone()
+            },
+            Cast::Raw {
+                path,
+                ptr,
+                target: quote!(#to_elem),
+            },
+            check,
+        ));
+    }
+
+    Ok(expanded)
+}
+
+fn expand_function_body(function: Function, cast: Cast, check: Option<&Check>) -> TokenStream2 {
     let Function {
         attrs,
         vis,
@@ -378,29 +1315,166 @@ fn expand_function_body(function: Function) -> TokenStream2 {
     // the caller to reject the expanded code.
     let macro_generated_unsafe = quote!(unsafe);
 
+    let cast_expr = match cast {
+        Cast::Transmute => quote! {
+            ::ref_cast::#private::transmute::<#from_type, #to_type>(#arg)
+        },
+        Cast::Raw { path, ptr, target } => quote! {
+            #path::from_raw(#path::into_raw(#arg) as #ptr #target)
+        },
+    };
+
+    let cast = quote! {
+        #allow_unused_unsafe // in case they are building with deny(unsafe_op_in_unsafe_fn)
+        #[allow(clippy::transmute_ptr_to_ptr)]
+        #macro_generated_unsafe {
+            #cast_expr
+        }
+    };
+    let checked_cast = quote! {
+        #allow_unused_unsafe
+        #[allow(clippy::transmute_ptr_to_ptr)]
+        let to = #macro_generated_unsafe {
+            #cast_expr
+        };
+    };
+
+    let (output, body) = match check {
+        None => (to_type.to_token_stream(), cast),
+        Some(check) => (
+            check.output(&to_type).to_token_stream(),
+            check.body(&arg, &checked_cast),
+        ),
+    };
+
     quote_spanned! {semi_token.span=>
         #(#attrs)*
         #inline_attr
         #vis #constness #asyncness #unsafety #abi
-        #fn_token #ident #generics #args #arrow_token #to_type {
-            // check lifetime
+        #fn_token #ident #generics #args #arrow_token #output {
+            // check lifetime, and check same module
             let _ = || {
-                ::ref_cast::#private::ref_cast_custom::<#from_type, #to_type>(#arg);
+                let current_module = ::ref_cast::#private::ref_cast_custom::<#from_type, #to_type>(&#arg);
+                current_module.map(|current_module| current_module.ref_cast_scope)
             };
 
             // check same crate
             let _ = ::ref_cast::#private::CurrentCrate::<#from_type, #to_type> {};
 
-            #allow_unused_unsafe // in case they are building with deny(unsafe_op_in_unsafe_fn)
-            #[allow(clippy::transmute_ptr_to_ptr)]
-            #macro_generated_unsafe {
-                ::ref_cast::#private::transmute::<#from_type, #to_type>(#arg)
+            #body
+        }
+    }
+}
+
+// The first `as` outside of angle brackets, so that a qualified path like
+// `<T as Trait>::Assoc` in the expression or target type is not split.
+fn find_top_level_as(tokens: &[TokenTree]) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut prev_punct = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Punct(punct) => {
+                match punct.as_char() {
+                    '<' => depth += 1,
+                    // `->` and `=>` are not closing brackets.
+                    '>' if !matches!(prev_punct, Some('-' | '=')) => {
+                        depth = depth.saturating_sub(1);
+                    }
+                    _ => {}
+                }
+                prev_punct = Some(punct.as_char());
+                continue;
             }
+            TokenTree::Ident(ident) if ident == "as" && depth == 0 => return Some(i),
+            _ => {}
+        }
+        prev_punct = None;
+    }
+    None
+}
+
+fn expand_cast_expr(input: TokenStream2) -> Result<TokenStream2> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let Some(as_token) = find_top_level_as(&tokens).filter(|&i| i > 0) else {
+        return Err(Error::new(
+            Span::call_site(),
+            "expected `ref_cast!(expr as &Type)`",
+        ));
+    };
+
+    let expr = tokens[..as_token].iter().cloned().collect::<TokenStream2>();
+    let to_type: Type = syn::parse2(tokens[as_token + 1..].iter().cloned().collect())?;
+    let Type::Reference(reference) = &to_type else {
+        return Err(Error::new_spanned(
+            to_type,
+            "ref_cast! requires a cast to &T or &mut T",
+        ));
+    };
+    let target = &reference.elem;
+
+    // See the comment in expand_function_body.
+    let macro_generated_unsafe = quote!(unsafe);
+
+    Ok(quote! {
+        {
+            let from = #expr;
+
+            // check same crate
+            let _ = ::ref_cast::#private::TargetCrate::<#target> {};
+
+            // check same module
+            let _ = |current_module: ::ref_cast::#private::TargetModule<#target>| {
+                current_module.ref_cast_scope
+            };
+
+            // check lifetime, and check layout
+            #[allow(unused_unsafe)]
+            let to = #macro_generated_unsafe {
+                ::ref_cast::#private::ref_cast_expr::<_, #to_type>(from)
+            };
+            to
         }
+    })
+}
+
+fn expand_transparent(derive: &Ident, input: &DeriveInput) -> TokenStream2 {
+    // A repr given explicitly, like repr(C), is left for check_repr to accept
+    // or reject. Adding repr(transparent) next to it would be a conflict.
+    let has_repr = input.attrs.iter().any(|attr| attr.path().is_ident("repr"));
+
+    let repr = if has_repr {
+        None
+    } else {
+        Some(quote!(#[repr(transparent)]))
+    };
+
+    quote! {
+        #[derive(::ref_cast::#derive)]
+        #repr
+        #input
     }
 }
 
+fn is_packed(input: &DeriveInput) -> bool {
+    let mut is_packed = false;
+    for attr in &input.attrs {
+        if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("packed") {
+                    is_packed = true;
+                }
+                if meta.input.peek(token::Paren) {
+                    let _: TokenTree = meta.input.parse()?;
+                }
+                Ok(())
+            });
+        }
+    }
+    is_packed
+}
+
 fn check_repr(input: &DeriveInput) -> Result<()> {
+    let is_enum = matches!(input.data, Data::Enum(_));
     let mut has_repr = false;
     let mut errors = None;
     let mut push_error = |error| match &mut errors {
@@ -413,8 +1487,17 @@ fn check_repr(input: &DeriveInput) -> Result<()> {
             if let Err(error) = attr.parse_args_with(|input: ParseStream| {
                 while !input.is_empty() {
                     let path = input.call(Path::parse_mod_style)?;
-                    if path.is_ident("transparent") || path.is_ident("C") {
+                    if path.is_ident("transparent") {
+                        has_repr = true;
+                    } else if path.is_ident("C") {
                         has_repr = true;
+                        if is_enum {
+                            // repr(C) on an enum includes a discriminant.
+                            push_error(Error::new_spanned(
+                                path,
+                                "RefCast requires #[repr(transparent)] on enums",
+                            ));
+                        }
                     } else if path.is_ident("packed") {
                         // ignore
                     } else {
@@ -465,22 +1548,39 @@ fn check_repr(input: &DeriveInput) -> Result<()> {
 
 type Fields = Punctuated<Field, Token![,]>;
 
-fn fields(input: &DeriveInput) -> Result<&Fields> {
+fn fields(input: &DeriveInput) -> Result<(Option<&Ident>, &Fields)> {
     use syn::Fields;
 
     match &input.data {
         Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => Ok(&fields.named),
-            Fields::Unnamed(fields) => Ok(&fields.unnamed),
+            Fields::Named(fields) => Ok((None, &fields.named)),
+            Fields::Unnamed(fields) => Ok((None, &fields.unnamed)),
             Fields::Unit => Err(Error::new(
                 Span::call_site(),
                 "RefCast does not support unit structs",
             )),
         },
-        Data::Enum(_) => Err(Error::new(
-            Span::call_site(),
-            "RefCast does not support enums",
-        )),
+        Data::Enum(data) => match data.variants.len() {
+            0 => Err(Error::new(
+                Span::call_site(),
+                "RefCast does not support enums without variants",
+            )),
+            1 => {
+                let variant = &data.variants[0];
+                match &variant.fields {
+                    Fields::Named(fields) => Ok((Some(&variant.ident), &fields.named)),
+                    Fields::Unnamed(fields) => Ok((Some(&variant.ident), &fields.unnamed)),
+                    Fields::Unit => Err(Error::new_spanned(
+                        variant,
+                        "RefCast does not support unit variants",
+                    )),
+                }
+            }
+            _ => Err(Error::new(
+                Span::call_site(),
+                "RefCast does not support enums with more than one variant",
+            )),
+        },
         Data::Union(_) => Err(Error::new(
             Span::call_site(),
             "RefCast does not support unions",
@@ -488,16 +1588,20 @@ fn fields(input: &DeriveInput) -> Result<&Fields> {
     }
 }
 
-fn only_field_ty(fields: &Fields) -> Result<&Type> {
+fn only_field(fields: &Fields) -> Result<(Member, &Type)> {
     let is_trivial = decide_trivial(fields)?;
     let mut only_field = None;
 
-    for field in fields {
+    for (i, field) in fields.iter().enumerate() {
         if !is_trivial(field)? {
             if only_field.take().is_some() {
                 break;
             }
-            only_field = Some(&field.ty);
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+            only_field = Some((member, &field.ty));
         }
     }
 
@@ -523,6 +1627,28 @@ fn trivial_fields(fields: &Fields) -> Result<Vec<&Type>> {
 }
 
 fn decide_trivial(fields: &Fields) -> Result<fn(&Field) -> Result<bool>> {
+    let mut has_from = false;
+    for field in fields {
+        if attr::is_from(field)? {
+            if has_from {
+                return Err(Error::new_spanned(
+                    field,
+                    "only one field can be marked #[ref_cast(from)]",
+                ));
+            }
+            if is_explicit_trivial(field)? {
+                return Err(Error::new_spanned(
+                    field,
+                    "field marked #[ref_cast(from)] cannot be #[trivial]",
+                ));
+            }
+            has_from = true;
+        }
+    }
+    if has_from {
+        return Ok(is_not_from);
+    }
+
     for field in fields {
         if is_explicit_trivial(field)? {
             return Ok(is_explicit_trivial);
@@ -531,6 +1657,10 @@ fn decide_trivial(fields: &Fields) -> Result<fn(&Field) -> Result<bool>> {
     Ok(is_implicit_trivial)
 }
 
+fn is_not_from(field: &Field) -> Result<bool> {
+    attr::is_from(field).map(|is_from| !is_from)
+}
+
 #[allow(clippy::unnecessary_wraps)] // match signature of is_explicit_trivial
 fn is_implicit_trivial(field: &Field) -> Result<bool> {
     match &field.ty {
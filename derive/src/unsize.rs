@@ -0,0 +1,284 @@
+use crate::impls::Wrapper;
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{parse_quote, Error, GenericParam, Generics, Result, Type, TypeParamBound};
+
+// Expand `#[ref_cast(unsize)]`, which converts &Wrapper<[T; N]> to
+// &Wrapper<[T]> for a wrapper whose field is a ?Sized type parameter.
+pub fn array_to_slice(wrapper: &Wrapper) -> Result<TokenStream> {
+    let name = &wrapper.input.ident;
+    let vis = &wrapper.input.vis;
+    let generics = &wrapper.input.generics;
+    let param = field_type_param(wrapper, "ref_cast(unsize)")?;
+
+    let array = quote!([__T; __N]);
+    let slice = quote!([__T]);
+
+    let impl_generics = impl_generics(
+        generics,
+        param,
+        vec![parse_quote!(__T), parse_quote!(const __N: usize)],
+    );
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+
+    let mut predicates = substitute_predicates(generics, param, &array);
+    predicates.extend(substitute_predicates(generics, param, &slice));
+
+    let self_ty = type_args(generics, param, &array);
+    let target_ty = type_args(generics, param, &slice);
+    let self_field = wrapper.field(quote!(self));
+    let self_field_mut = wrapper.field_mut(quote!(self));
+
+    Ok(quote! {
+        #[allow(dead_code)]
+        impl #impl_generics #name #self_ty
+        where
+            #(#predicates,)*
+        {
+            #vis fn unsize(&self) -> &#name #target_ty {
+                <#name #target_ty as ::ref_cast::RefCast>::ref_cast(#self_field)
+            }
+
+            #vis fn unsize_mut(&mut self) -> &mut #name #target_ty {
+                <#name #target_ty as ::ref_cast::RefCast>::ref_cast_mut(#self_field_mut)
+            }
+        }
+    })
+}
+
+// Expand `#[ref_cast(unsize = dyn Trait)]`, which converts &Wrapper<T> to
+// &Wrapper<dyn Trait> for any sized T implementing the trait.
+pub fn to_dyn(wrapper: &Wrapper, dyn_ty: &Type) -> Result<TokenStream> {
+    let name = &wrapper.input.ident;
+    let vis = &wrapper.input.vis;
+    let generics = &wrapper.input.generics;
+    let param = field_type_param(wrapper, "ref_cast(unsize = ...)")?;
+
+    let Type::TraitObject(trait_object) = dyn_ty else {
+        unreachable!();
+    };
+    let mut bounds = trait_object.bounds.clone();
+    if !bounds
+        .iter()
+        .any(|bound| matches!(bound, TypeParamBound::Lifetime(_)))
+    {
+        // Matches the default object lifetime of `&Wrapper<dyn Trait>`.
+        bounds.push(parse_quote!('static));
+    }
+
+    let impl_generics = impl_generics(generics, param, vec![parse_quote!(#param)]);
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+
+    let dyn_ty = quote!(#dyn_ty);
+    let bounded_dyn_ty = if trait_object.bounds.len() > 1 {
+        quote!((#dyn_ty))
+    } else {
+        dyn_ty.clone()
+    };
+    let mut predicates = substitute_predicates(generics, param, &quote!(#param));
+    predicates.extend(substitute_predicates(generics, param, &bounded_dyn_ty));
+    predicates.push(quote!(#param: #bounds));
+
+    let self_ty = type_args(generics, param, &quote!(#param));
+    let target_ty = type_args(generics, param, &dyn_ty);
+    let self_field = wrapper.field(quote!(self));
+    let self_field_mut = wrapper.field_mut(quote!(self));
+
+    Ok(quote! {
+        #[allow(dead_code)]
+        impl #impl_generics #name #self_ty
+        where
+            #(#predicates,)*
+        {
+            #vis fn as_dyn(&self) -> &#name #target_ty {
+                let field: &#bounded_dyn_ty = #self_field;
+                <#name #target_ty as ::ref_cast::RefCast>::ref_cast(field)
+            }
+
+            #vis fn as_dyn_mut(&mut self) -> &mut #name #target_ty {
+                let field: &mut #bounded_dyn_ty = #self_field_mut;
+                <#name #target_ty as ::ref_cast::RefCast>::ref_cast_mut(field)
+            }
+        }
+    })
+}
+
+// The original generics without bounds, which are all moved into the where
+// clause, and with `param` replaced by `replacement`.
+fn impl_generics(generics: &Generics, param: &Ident, replacement: Vec<GenericParam>) -> Generics {
+    let mut replacement = Some(replacement);
+    let params = generics
+        .params
+        .iter()
+        .flat_map(|generic_param| match generic_param {
+            GenericParam::Type(ty) if ty.ident == *param => replacement.take().unwrap(),
+            GenericParam::Type(ty) => {
+                let mut ty = ty.clone();
+                ty.bounds.clear();
+                vec![GenericParam::Type(ty)]
+            }
+            GenericParam::Lifetime(lifetime) => {
+                let mut lifetime = lifetime.clone();
+                lifetime.bounds.clear();
+                vec![GenericParam::Lifetime(lifetime)]
+            }
+            GenericParam::Const(_) => vec![generic_param.clone()],
+        })
+        .collect();
+    Generics {
+        params,
+        where_clause: None,
+        ..generics.clone()
+    }
+}
+
+// The type parameter that is the type of the field, which must be ?Sized.
+fn field_type_param<'a>(wrapper: &'a Wrapper, option: &str) -> Result<&'a Ident> {
+    let generics = &wrapper.input.generics;
+    let param = match wrapper.from {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.get_ident(),
+        _ => None,
+    };
+    let Some(param) = param.and_then(|ident| {
+        generics
+            .type_params()
+            .find(|param| param.ident == *ident)
+            .map(|param| &param.ident)
+    }) else {
+        return Err(Error::new_spanned(
+            wrapper.from,
+            format!("{option} requires the field's type to be a type parameter"),
+        ));
+    };
+
+    let is_maybe_sized = |bound: &TypeParamBound| match bound {
+        TypeParamBound::Trait(bound) => bound.maybe.is_some() && bound.path.is_ident("Sized"),
+        _ => false,
+    };
+    let maybe_sized = generics
+        .type_params()
+        .filter(|ty| ty.ident == *param)
+        .flat_map(|ty| &ty.bounds)
+        .chain(generics.where_clause.iter().flat_map(|where_clause| {
+            where_clause
+                .predicates
+                .iter()
+                .filter_map(|predicate| match predicate {
+                    syn::WherePredicate::Type(predicate)
+                        if is_ident(&predicate.bounded_ty, param) =>
+                    {
+                        Some(&predicate.bounds)
+                    }
+                    _ => None,
+                })
+                .flatten()
+        }))
+        .any(is_maybe_sized);
+    if !maybe_sized {
+        return Err(Error::new_spanned(
+            param,
+            format!("{option} requires the type parameter to be ?Sized"),
+        ));
+    }
+
+    Ok(param)
+}
+
+// The generic arguments of the wrapper type, with `param` replaced.
+fn type_args(generics: &Generics, param: &Ident, replacement: &TokenStream) -> TokenStream {
+    let args = generics
+        .params
+        .iter()
+        .map(|generic_param| match generic_param {
+            GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_token_stream(),
+            GenericParam::Type(ty) if ty.ident == *param => replacement.clone(),
+            GenericParam::Type(ty) => ty.ident.to_token_stream(),
+            GenericParam::Const(constant) => constant.ident.to_token_stream(),
+        });
+    quote!(<#(#args),*>)
+}
+
+// Every bound in the original generics, with `param` replaced. `?Sized` bounds
+// are dropped since they are only allowed on type parameters.
+fn substitute_predicates(
+    generics: &Generics,
+    param: &Ident,
+    replacement: &TokenStream,
+) -> Vec<TokenStream> {
+    let mut predicates = Vec::new();
+
+    let substitute = |bounded: TokenStream, bounds: Vec<&TypeParamBound>| {
+        let bounds: Vec<&TypeParamBound> = bounds
+            .into_iter()
+            .filter(|bound| match bound {
+                TypeParamBound::Trait(bound) => bound.maybe.is_none(),
+                _ => true,
+            })
+            .collect();
+        if !bounds.is_empty() {
+            let bounded = replace_ident(bounded, param, replacement);
+            let bounds = replace_ident(quote!(#(#bounds)+*), param, replacement);
+            Some(quote!(#bounded: #bounds))
+        } else {
+            None
+        }
+    };
+
+    for generic_param in &generics.params {
+        match generic_param {
+            GenericParam::Type(ty) => predicates.extend(substitute(
+                ty.ident.to_token_stream(),
+                ty.bounds.iter().collect(),
+            )),
+            GenericParam::Lifetime(param) => {
+                if !param.bounds.is_empty() {
+                    let lifetime = &param.lifetime;
+                    let bounds = &param.bounds;
+                    predicates.push(quote!(#lifetime: #bounds));
+                }
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            match predicate {
+                syn::WherePredicate::Type(predicate) => predicates.extend(substitute(
+                    predicate.bounded_ty.to_token_stream(),
+                    predicate.bounds.iter().collect(),
+                )),
+                _ => predicates.push(replace_ident(
+                    predicate.to_token_stream(),
+                    param,
+                    replacement,
+                )),
+            }
+        }
+    }
+
+    predicates
+}
+
+fn is_ident(ty: &Type, ident: &Ident) -> bool {
+    match ty {
+        Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident(ident),
+        _ => false,
+    }
+}
+
+fn replace_ident(tokens: TokenStream, ident: &Ident, replacement: &TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ref i) if i == ident => replacement.clone(),
+            TokenTree::Group(group) => {
+                let stream = replace_ident(group.stream(), ident, replacement);
+                let mut replaced = Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into_token_stream()
+            }
+            token => token.into_token_stream(),
+        })
+        .collect()
+}
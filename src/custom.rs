@@ -1,13 +1,33 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+use core::mem::ManuallyDrop;
+
 // Not public API. Use #[derive(RefCastCustom)] and #[ref_cast_custom].
 #[doc(hidden)]
 pub unsafe trait RefCastCustom<From: ?Sized> {
     type CurrentCrate;
+    type CurrentModule;
+    type Alignment;
     fn __static_assert() {}
 }
 
+// RefCastCustom::Alignment of types whose alignment equals that of From.
+#[doc(hidden)]
+pub enum SameAlign {}
+
+// RefCastCustom::Alignment of #[repr(packed)] types, which may have a smaller
+// alignment than From.
+#[doc(hidden)]
+pub enum MaybeLowerAlign {}
+
 #[doc(hidden)]
 pub unsafe trait RefCastOkay<From>: Sealed<From> {
     type CurrentCrate;
+    type CurrentModule;
     type Target: ?Sized;
 }
 
@@ -17,6 +37,7 @@ where
     To: ?Sized + RefCastCustom<From>,
 {
     type CurrentCrate = To::CurrentCrate;
+    type CurrentModule = To::CurrentModule;
     type Target = To;
 }
 
@@ -26,6 +47,7 @@ where
     To: ?Sized + RefCastCustom<From>,
 {
     type CurrentCrate = To::CurrentCrate;
+    type CurrentModule = To::CurrentModule;
     type Target = To;
 }
 
@@ -46,12 +68,148 @@ where
 {
 }
 
+// Smart pointers for #[ref_cast_custom(boxed = ..., rc = ..., arc = ...)]. The
+// allocation is freed with the layout of the target type, so its alignment must
+// match.
+#[cfg(feature = "alloc")]
+macro_rules! smart_pointer_okay {
+    ($($(#[$cfg:meta])* $pointer:ident)*) => {
+        $(
+            $(#[$cfg])*
+            unsafe impl<From, To> RefCastOkay<$pointer<From>> for $pointer<To>
+            where
+                From: ?Sized,
+                To: ?Sized + RefCastCustom<From, Alignment = SameAlign>,
+            {
+                type CurrentCrate = To::CurrentCrate;
+                type CurrentModule = To::CurrentModule;
+                type Target = To;
+            }
+
+            $(#[$cfg])*
+            impl<From, To> Sealed<$pointer<From>> for $pointer<To>
+            where
+                From: ?Sized,
+                To: ?Sized + RefCastCustom<From, Alignment = SameAlign>,
+            {
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+smart_pointer_okay! {
+    Box
+    Rc
+    #[cfg(target_has_atomic = "ptr")]
+    Arc
+}
+
 #[doc(hidden)]
 pub type CurrentCrate<From, To> = <To as RefCastOkay<From>>::CurrentCrate;
 
 #[doc(hidden)]
-pub fn ref_cast_custom<From, To>(_arg: From)
+pub type CurrentModule<From, To> = <To as RefCastOkay<From>>::CurrentModule;
+
+// The CurrentModule of types without #[ref_cast(scope = ...)]. The generated
+// #[ref_cast_custom] function reads this field, which derive(RefCastCustom)
+// otherwise declares with the requested visibility.
+#[doc(hidden)]
+pub struct AnyModule {
+    pub ref_cast_scope: (),
+}
+
+#[doc(hidden)]
+pub fn ref_cast_custom<From, To>(_arg: &From) -> Option<CurrentModule<From, To>>
+where
+    To: RefCastOkay<From>,
+{
+    None
+}
+
+// Implemented by derive(RefCastCustom) for the type itself, independent of the
+// source type, so that ref_cast! can perform the crate and module checks on a
+// target type spelled at the call site while the source type is inferred.
+#[doc(hidden)]
+pub trait RefCastTarget {
+    type CurrentCrate;
+    type CurrentModule;
+}
+
+#[doc(hidden)]
+pub type TargetCrate<To> = <To as RefCastTarget>::CurrentCrate;
+
+#[doc(hidden)]
+pub type TargetModule<To> = <To as RefCastTarget>::CurrentModule;
+
+#[doc(hidden)]
+pub const unsafe fn ref_cast_expr<From, To>(from: From) -> To
 where
     To: RefCastOkay<From>,
+{
+    union Cast<From, To> {
+        from: ManuallyDrop<From>,
+        to: ManuallyDrop<To>,
+    }
+
+    let cast = Cast {
+        from: ManuallyDrop::new(from),
+    };
+    ManuallyDrop::into_inner(unsafe { cast.to })
+}
+
+/// Marker for types that `#[ref_cast_custom]` functions can cast to from
+/// `From`.
+///
+/// `derive(RefCastCustom)` establishes this relationship for the type of the
+/// single non-trivial field, and for the sources of any `#[ref_cast(via =
+/// ...)]` types, unless the type is `#[repr(packed)]` and so may have a smaller
+/// alignment. Generic code can use it as a bound, for example to write adapters
+/// over slices, boxes or options of such types once.
+///
+/// This trait does not provide a cast, and the restriction that only the crate
+/// defining the type may write `#[ref_cast_custom]` functions for it stays in
+/// place. What it guarantees is that `Self` has the same layout as `From`. It
+/// says nothing about which values of `From` are valid values of `Self`, as the
+/// type's own casting functions may check invariants, so unsafe code relying
+/// on this trait must only reinterpret values already known to be valid for
+/// `Self`.
+///
+/// ```
+/// use ref_cast::{ref_cast_custom, RefCastCustom, RefCastCustomFrom};
+///
+/// #[derive(RefCastCustom)]
+/// #[repr(transparent)]
+/// pub struct Celsius(f64);
+///
+/// impl Celsius {
+///     #[ref_cast_custom]
+///     fn new_unchecked(degrees: &f64) -> &Self;
+///
+///     pub fn new(degrees: &f64) -> Option<&Self> {
+///         if *degrees >= -273.15 {
+///             Some(Self::new_unchecked(degrees))
+///         } else {
+///             None
+///         }
+///     }
+/// }
+///
+/// // Every valid wrapper is a valid f64, so this direction is always sound.
+/// fn unwrap_all<T: RefCastCustomFrom<f64>>(wrappers: &[T]) -> &[f64] {
+///     unsafe { &*(wrappers as *const [T] as *const [f64]) }
+/// }
+/// ```
+///
+/// # Safety
+///
+/// `Self` must have the same size, alignment and pointer metadata as `From`,
+/// as is the case for a `#[repr(transparent)]` struct around `From`.
+pub unsafe trait RefCastCustomFrom<From: ?Sized> {}
+
+unsafe impl<T, From> RefCastCustomFrom<From> for T
+where
+    T: ?Sized + RefCastCustom<From, Alignment = SameAlign>,
+    From: ?Sized,
 {
 }
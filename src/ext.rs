@@ -0,0 +1,48 @@
+use crate::RefCast;
+
+/// Cast from the source side, as in `value.cast_ref::<Wrapper>()`.
+///
+/// This trait is implemented for every type and builds on [`RefCast`], so that
+/// casts can be written in method-call order.
+///
+/// ```
+/// use ref_cast::{RefCast, RefCastExt};
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// struct Name(str);
+///
+/// let name = "main".cast_ref::<Name>();
+///
+/// let names: Vec<&Name> = ["main", "test"]
+///     .into_iter()
+///     .map(RefCastExt::cast_ref::<Name>)
+///     .collect();
+/// ```
+pub trait RefCastExt {
+    fn cast_ref<U>(&self) -> &U
+    where
+        U: ?Sized + RefCast<From = Self>;
+
+    fn cast_mut<U>(&mut self) -> &mut U
+    where
+        U: ?Sized + RefCast<From = Self>;
+}
+
+impl<T: ?Sized> RefCastExt for T {
+    #[inline]
+    fn cast_ref<U>(&self) -> &U
+    where
+        U: ?Sized + RefCast<From = Self>,
+    {
+        U::ref_cast(self)
+    }
+
+    #[inline]
+    fn cast_mut<U>(&mut self) -> &mut U
+    where
+        U: ?Sized + RefCast<From = Self>,
+    {
+        U::ref_cast_mut(self)
+    }
+}
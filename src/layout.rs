@@ -58,3 +58,22 @@ pub fn assert_layout<Outer: ?Sized, Inner: ?Sized>(
     #[cfg(not(no_intrinsic_type_name))]
     let _ = name;
 }
+
+// LayoutUnsized has nothing to compare for unsized types, whose size and
+// alignment come from the pointer metadata (a slice length or a vtable). Check
+// the metadata survived the cast by comparing the layouts of the referents.
+#[doc(hidden)]
+#[inline]
+pub fn assert_layout_of_val<Outer: ?Sized, Inner: ?Sized>(
+    name: &'static str,
+    outer: &Outer,
+    inner: &Inner,
+) {
+    assert_layout::<Outer, Inner>(
+        name,
+        mem::size_of_val(outer),
+        mem::size_of_val(inner),
+        mem::align_of_val(outer),
+        mem::align_of_val(inner),
+    );
+}
@@ -148,11 +148,24 @@
     clippy::needless_pass_by_value
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 mod custom;
+mod ext;
 mod layout;
+mod newtype;
+mod pin;
+mod transparent;
 mod trivial;
+#[cfg(feature = "alloc")]
+mod vec;
 
-pub use ref_cast_impl::{ref_cast_custom, RefCast, RefCastCustom};
+pub use crate::custom::RefCastCustomFrom;
+pub use crate::ext::RefCastExt;
+#[cfg(feature = "alloc")]
+pub use crate::vec::{cast_vec, uncast_vec};
+pub use ref_cast_impl::{ref_cast, ref_cast_custom, transparent, RefCast, RefCastCustom};
 
 /// Safely cast `&T` to `&U` where the struct `U` contains a single field of
 /// type `T`.
@@ -173,6 +186,20 @@ pub use ref_cast_impl::{ref_cast_custom, RefCast, RefCastCustom};
 /// }
 /// ```
 ///
+/// Slices and arrays of a type that derives `RefCast` are themselves
+/// `RefCast`, with `From = [T::From]` and `From = [T::From; N]` respectively,
+/// unless the type is `#[repr(packed)]`.
+///
+/// ```
+/// # use ref_cast::RefCast;
+/// #
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// struct NodeId(u32);
+///
+/// let ids: &[NodeId] = RefCast::ref_cast(&[1, 2, 3][..]);
+/// ```
+///
 /// See the [crate-level documentation][crate] for usage examples!
 pub trait RefCast {
     type From: ?Sized;
@@ -180,6 +207,68 @@ pub trait RefCast {
     fn ref_cast_mut(from: &mut Self::From) -> &mut Self;
 }
 
+/// Safely cast `&T` to `&Self`, where `Self` may be castable from more than one
+/// type `T`.
+///
+/// `derive(RefCast)` implements this trait for `T = <Self as RefCast>::From`.
+/// Nested wrappers can additionally be cast from the source type of the type
+/// they wrap, using `#[ref_cast(via = ...)]`.
+///
+/// ```
+/// use ref_cast::{RefCast, RefCastFrom};
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// struct Utf8([u8]);
+///
+/// #[derive(RefCast)]
+/// #[ref_cast(via = Utf8)]
+/// #[repr(transparent)]
+/// struct Ident(Utf8);
+///
+/// // `&[u8]` can be cast directly to `&Ident`.
+/// let ident = Ident::ref_cast_from(&b"main"[..]);
+/// ```
+///
+/// Other source types can be listed in `#[ref_cast(cast_from(...))]`, which
+/// goes through their `DerefMut` impl. Unlike `RefCast`, this trait does not
+/// guarantee that `T` and `Self` have the same layout.
+pub trait RefCastFrom<From: ?Sized> {
+    fn ref_cast_from(from: &From) -> &Self;
+    fn ref_cast_from_mut(from: &mut From) -> &mut Self;
+}
+
+/// Cast `&From` to `&To`, choosing the [`RefCastFrom`] impl by type inference.
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// struct Key(str);
+///
+/// let key = ref_cast::cast::<Key, _>("key");
+/// ```
+#[inline]
+pub fn cast<To, From>(from: &From) -> &To
+where
+    To: ?Sized + RefCastFrom<From>,
+    From: ?Sized,
+{
+    To::ref_cast_from(from)
+}
+
+/// Cast `&mut From` to `&mut To`, choosing the [`RefCastFrom`] impl by type
+/// inference.
+#[inline]
+pub fn cast_mut<To, From>(from: &mut From) -> &mut To
+where
+    To: ?Sized + RefCastFrom<From>,
+    From: ?Sized,
+{
+    To::ref_cast_from_mut(from)
+}
+
 mod private;
 
 include!(concat!(env!("OUT_DIR"), "/private.rs"));
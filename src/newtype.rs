@@ -0,0 +1,58 @@
+/// Declare structs that derive [`RefCast`][crate::RefCast] with
+/// `#[repr(transparent)]` applied.
+///
+/// The conversion and forwarding impls to generate are selected with the same
+/// `#[ref_cast(...)]` attribute options as `derive(RefCast)` accepts, and any
+/// other attributes are passed through to the struct. Several structs can be
+/// declared in one invocation.
+///
+/// ```
+/// ref_cast::newtype! {
+///     /// A host name.
+///     #[ref_cast(as_ref, deref, forward(Display, PartialEq, Hash))]
+///     pub struct Host(str);
+///
+///     #[derive(Debug)]
+///     #[ref_cast(as_ref, deref)]
+///     pub struct Payload([u8]);
+///
+///     #[ref_cast(as_ref)]
+///     pub struct Tagged<T: ?Sized> {
+///         value: T,
+///     }
+/// }
+///
+/// use ref_cast::RefCast;
+///
+/// let host = Host::ref_cast("example.com");
+/// assert_eq!(host.to_string(), "example.com");
+/// assert_eq!(host.len(), 11);
+/// ```
+#[macro_export]
+macro_rules! newtype {
+    () => {};
+
+    (@item [$($item:tt)*] ; $($rest:tt)*) => {
+        #[derive($crate::RefCast)]
+        #[repr(transparent)]
+        $($item)* ;
+
+        $crate::newtype! { $($rest)* }
+    };
+
+    (@item [$($item:tt)*] { $($fields:tt)* } $($rest:tt)*) => {
+        #[derive($crate::RefCast)]
+        #[repr(transparent)]
+        $($item)* { $($fields)* }
+
+        $crate::newtype! { $($rest)* }
+    };
+
+    (@item [$($item:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::newtype! { @item [$($item)* $next] $($rest)* }
+    };
+
+    ($(#[$attr:meta])* $vis:vis struct $name:ident $($rest:tt)*) => {
+        $crate::newtype! { @item [$(#[$attr])* $vis struct $name] $($rest)* }
+    };
+}
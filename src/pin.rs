@@ -0,0 +1,8 @@
+use core::marker::PhantomData;
+
+// Used by #[ref_cast(pin)] to write an Unpin impl with the same bounds as the
+// auto trait impl, so that a conflicting handwritten Unpin impl is rejected.
+// The lifetime keeps bounds on concrete field types, like PhantomPinned, from
+// being rejected as trivially false. Not public API.
+#[doc(hidden)]
+pub struct PinnedField<'a, T: ?Sized>(PhantomData<&'a ()>, T);
@@ -1,8 +1,25 @@
 #[doc(hidden)]
-pub use crate::custom::{ref_cast_custom, CurrentCrate, RefCastCustom};
+pub use crate::custom::{
+    ref_cast_custom, ref_cast_expr, AnyModule, CurrentCrate, CurrentModule, MaybeLowerAlign,
+    RefCastCustom, RefCastTarget, SameAlign, TargetCrate, TargetModule,
+};
 #[doc(hidden)]
-pub use crate::layout::{assert_layout, Layout, LayoutUnsized};
+pub use crate::layout::{assert_layout, assert_layout_of_val, Layout, LayoutUnsized};
 #[doc(hidden)]
-pub use crate::trivial::assert_trivial;
+pub use crate::pin::PinnedField;
+#[doc(hidden)]
+pub use crate::transparent::Transparent;
+#[doc(hidden)]
+pub use crate::trivial::{assert_trivial, Trivial};
 #[doc(hidden)]
 pub use core::mem::transmute;
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::rc::Rc;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+#[doc(hidden)]
+pub use alloc::sync::Arc;
@@ -0,0 +1,70 @@
+use crate::RefCast;
+
+// Implemented by derive(RefCast) to assert that Self has the same layout as
+// From, which a handwritten RefCast impl does not guarantee. Not implemented
+// for repr(packed) types, whose alignment may be less than From's. Not public
+// API.
+#[doc(hidden)]
+pub unsafe trait Transparent<From: ?Sized> {}
+
+unsafe impl<T, U> Transparent<[T]> for [U] where U: Transparent<T> {}
+
+unsafe impl<T, U, const N: usize> Transparent<[T; N]> for [U; N] where U: Transparent<T> {}
+
+impl<U> RefCast for [U]
+where
+    U: RefCast + Transparent<<U as RefCast>::From>,
+    U::From: Sized,
+{
+    type From = [U::From];
+
+    #[inline]
+    fn ref_cast(from: &Self::From) -> &Self {
+        #[cfg(debug_assertions)]
+        assert_element_layout::<U, U::From>("[T]");
+        unsafe { &*(from as *const [U::From] as *const [U]) }
+    }
+
+    #[inline]
+    fn ref_cast_mut(from: &mut Self::From) -> &mut Self {
+        #[cfg(debug_assertions)]
+        assert_element_layout::<U, U::From>("[T]");
+        unsafe { &mut *(from as *mut [U::From] as *mut [U]) }
+    }
+}
+
+impl<U, const N: usize> RefCast for [U; N]
+where
+    U: RefCast + Transparent<<U as RefCast>::From>,
+    U::From: Sized,
+{
+    type From = [U::From; N];
+
+    #[inline]
+    fn ref_cast(from: &Self::From) -> &Self {
+        #[cfg(debug_assertions)]
+        assert_element_layout::<U, U::From>("[T; N]");
+        unsafe { &*(from as *const [U::From; N]).cast::<[U; N]>() }
+    }
+
+    #[inline]
+    fn ref_cast_mut(from: &mut Self::From) -> &mut Self {
+        #[cfg(debug_assertions)]
+        assert_element_layout::<U, U::From>("[T; N]");
+        unsafe { &mut *(from as *mut [U::From; N]).cast::<[U; N]>() }
+    }
+}
+
+#[cfg(debug_assertions)]
+pub(crate) fn assert_element_layout<Outer, Inner>(name: &'static str) {
+    use crate::layout::assert_layout;
+    use core::mem;
+
+    assert_layout::<Outer, Inner>(
+        name,
+        mem::size_of::<Outer>(),
+        mem::size_of::<Inner>(),
+        mem::align_of::<Outer>(),
+        mem::align_of::<Inner>(),
+    );
+}
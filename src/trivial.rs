@@ -3,13 +3,22 @@ use core::marker::PhantomData;
 use core::marker::PhantomPinned;
 
 #[doc(hidden)]
-pub trait Trivial {}
+pub trait Trivial {
+    const VALUE: Self;
+}
 
-impl Trivial for () {}
-impl<T: ?Sized> Trivial for PhantomData<T> {}
+impl Trivial for () {
+    const VALUE: Self = ();
+}
+
+impl<T: ?Sized> Trivial for PhantomData<T> {
+    const VALUE: Self = PhantomData;
+}
 
 #[cfg(not(no_phantom_pinned))]
-impl Trivial for PhantomPinned {}
+impl Trivial for PhantomPinned {
+    const VALUE: Self = PhantomPinned;
+}
 
 #[doc(hidden)]
 pub fn assert_trivial<T: Trivial>() {}
@@ -0,0 +1,52 @@
+use crate::transparent::Transparent;
+use crate::RefCast;
+use alloc::vec::Vec;
+use core::mem::ManuallyDrop;
+
+/// Convert `Vec<U::From>` to `Vec<U>` in place, without reallocating.
+///
+/// This requires `U` to implement `RefCast` by `#[derive(RefCast)]`, which
+/// guarantees that `U` and `U::From` have the same size and alignment. Wrappers
+/// that are `#[repr(packed)]` are not accepted, as their alignment may differ.
+///
+/// *Available when the `alloc` feature is enabled.*
+///
+/// ```
+/// use ref_cast::RefCast;
+///
+/// #[derive(RefCast)]
+/// #[repr(transparent)]
+/// pub struct NodeId(u32);
+///
+/// let decoded: Vec<u32> = vec![3, 1, 4];
+/// let ids: Vec<NodeId> = ref_cast::cast_vec(decoded);
+/// assert_eq!(ids[2].0, 4);
+/// ```
+#[must_use]
+pub fn cast_vec<U>(vec: Vec<U::From>) -> Vec<U>
+where
+    U: RefCast + Transparent<<U as RefCast>::From>,
+    U::From: Sized,
+{
+    #[cfg(debug_assertions)]
+    crate::transparent::assert_element_layout::<U, U::From>("Vec<T>");
+    let mut vec = ManuallyDrop::new(vec);
+    let (ptr, len, capacity) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+    unsafe { Vec::from_raw_parts(ptr.cast::<U>(), len, capacity) }
+}
+
+/// Convert `Vec<U>` back to `Vec<U::From>` in place, without reallocating.
+///
+/// *Available when the `alloc` feature is enabled.*
+#[must_use]
+pub fn uncast_vec<U>(vec: Vec<U>) -> Vec<U::From>
+where
+    U: RefCast + Transparent<<U as RefCast>::From>,
+    U::From: Sized,
+{
+    #[cfg(debug_assertions)]
+    crate::transparent::assert_element_layout::<U, U::From>("Vec<T>");
+    let mut vec = ManuallyDrop::new(vec);
+    let (ptr, len, capacity) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+    unsafe { Vec::from_raw_parts(ptr.cast::<U::From>(), len, capacity) }
+}
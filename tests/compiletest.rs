@@ -4,4 +4,9 @@
 fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "alloc")]
+    t.compile_fail("tests/ui/alloc/*.rs");
+    // The notes listing RefCastOkay impls include Box, Rc and Arc with alloc.
+    #[cfg(not(feature = "alloc"))]
+    t.compile_fail("tests/ui/no-alloc/*.rs");
 }
@@ -5,3 +5,11 @@ use ref_cast::RefCastCustom;
 #[derive(RefCastCustom)]
 #[repr(transparent)]
 pub struct Struct(str);
+
+// Any crate that can write `ExtCapability {}` may define casts to `Shared`.
+pub struct ExtCapability {}
+
+#[derive(RefCastCustom)]
+#[ref_cast(capability = ExtCapability)]
+#[repr(transparent)]
+pub struct Shared(str);
@@ -0,0 +1,43 @@
+use ref_cast::RefCast;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(RefCast)]
+#[ref_cast(borrow(String, Box<str>))]
+#[repr(transparent)]
+pub struct Name(str);
+
+#[derive(RefCast)]
+#[ref_cast(borrow(Vec<T>))]
+#[repr(transparent)]
+pub struct Generic<T>([T]);
+
+#[test]
+fn test_hash_map() {
+    let mut map = HashMap::new();
+    map.insert("main".to_owned(), 1);
+    assert_eq!(map.get(Name::ref_cast("main")), Some(&1));
+    assert_eq!(map.get(Name::ref_cast("test")), None);
+}
+
+#[test]
+fn test_btree_map() {
+    let mut map = BTreeMap::new();
+    map.insert(Box::<str>::from("b"), 2);
+    map.insert(Box::<str>::from("a"), 1);
+    assert_eq!(map.get(Name::ref_cast("a")), Some(&1));
+    assert_eq!(map.get(Name::ref_cast("b")), Some(&2));
+}
+
+#[test]
+fn test_generic() {
+    let mut set = HashSet::new();
+    set.insert(vec![1, 2, 3]);
+    assert!(set.contains(Generic::ref_cast(&[1, 2, 3][..])));
+    assert!(!set.contains(Generic::ref_cast(&[1, 2][..])));
+}
+
+#[test]
+fn test_delegating_impls() {
+    assert!(Name::ref_cast("a") == Name::ref_cast("a"));
+    assert!(Name::ref_cast("a") < Name::ref_cast("b"));
+}
@@ -0,0 +1,55 @@
+use ref_cast::RefCast;
+use std::cmp::Ordering;
+
+#[derive(RefCast)]
+#[ref_cast(compare(PartialEq, PartialOrd))]
+#[repr(transparent)]
+pub struct Name(str);
+
+#[derive(RefCast)]
+#[ref_cast(compare(PartialEq))]
+#[repr(transparent)]
+pub struct Generic<T> {
+    value: T,
+}
+
+#[derive(RefCast)]
+#[ref_cast(compare(PartialEq))]
+#[repr(transparent)]
+pub struct Boxed<T>(Box<T>);
+
+#[derive(RefCast)]
+#[ref_cast(compare(PartialOrd))]
+#[repr(transparent)]
+pub struct OrdOnly(i32);
+
+#[test]
+fn test_eq() {
+    let name = Name::ref_cast("main");
+    assert!(name == "main");
+    assert!(*name == "main");
+    assert!(*name == *"main");
+    assert!("main" == *name);
+    assert!(*"main" == *name);
+    assert!(name != "test");
+
+    let one = &1;
+    let generic = Generic::ref_cast(one);
+    assert!(*generic == 1);
+    assert!(*generic == one);
+
+    let boxed = Box::new(1);
+    assert!(*Boxed::ref_cast(&boxed) == boxed);
+}
+
+#[test]
+fn test_ord() {
+    let name = Name::ref_cast("main");
+    assert_eq!(name.partial_cmp("lib"), Some(Ordering::Greater));
+    assert_eq!("lib".partial_cmp(name), Some(Ordering::Less));
+    assert!(*name < "test");
+    assert!("test" > *name);
+
+    assert!(*OrdOnly::ref_cast(&1) == 1);
+    assert!(*OrdOnly::ref_cast(&1) < 2);
+}
@@ -0,0 +1,71 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast)]
+#[ref_cast(as_ref, from_ref, deref)]
+#[repr(transparent)]
+pub struct Name(str);
+
+impl AsRef<Name> for Name {
+    fn as_ref(&self) -> &Name {
+        self
+    }
+}
+
+#[derive(RefCast)]
+#[ref_cast(as_ref, from_ref, deref)]
+#[repr(transparent)]
+pub struct Generic<T: ?Sized> {
+    value: T,
+}
+
+#[derive(RefCast)]
+#[ref_cast(as_ref)]
+#[repr(transparent)]
+pub struct AsRefBox<T>(Box<T>);
+
+#[derive(RefCast)]
+#[ref_cast(as_ref)]
+#[repr(transparent)]
+pub struct AsRefVec<T>(Vec<T>);
+
+fn len(name: &(impl AsRef<Name> + ?Sized)) -> usize {
+    name.as_ref().len()
+}
+
+#[test]
+fn test_as_ref() {
+    let name = Name::ref_cast("main");
+    assert_eq!(len("main"), 4);
+    assert_eq!(len(name), 4);
+    let s: &str = name.as_ref();
+    assert_eq!(s, "main");
+
+    let boxed = Box::new(1);
+    let wrapper = AsRefBox::ref_cast(&boxed);
+    assert_eq!(**AsRef::<Box<i32>>::as_ref(wrapper), 1);
+
+    let vec = vec![1];
+    let wrapper: &AsRefVec<i32> = vec.as_ref();
+    assert_eq!(wrapper.0, [1]);
+}
+
+#[test]
+fn test_from() {
+    let name: &Name = "main".into();
+    assert_eq!(&name.0, "main");
+
+    let mut value = 0;
+    let generic: &mut Generic<i32> = (&mut value).into();
+    generic.value += 1;
+    assert_eq!(value, 1);
+}
+
+#[test]
+fn test_deref() {
+    let name = Name::ref_cast("main");
+    assert!(name.starts_with("ma"));
+
+    let generic = Generic::ref_cast(&[1, 2, 3][..]);
+    assert_eq!(generic.len(), 3);
+    assert_eq!(AsRef::<[i32]>::as_ref(generic), [1, 2, 3]);
+}
@@ -16,3 +16,273 @@ mod forbid_unsafe {
 fn test_forbid_unsafe() {
     forbid_unsafe::Custom::new("...");
 }
+
+mod custom_from {
+    use ref_cast::{RefCast, RefCastCustom};
+
+    #[derive(RefCastCustom)]
+    #[repr(transparent)]
+    pub struct Ident(#[allow(dead_code)] str);
+
+    #[derive(RefCast)]
+    #[repr(transparent)]
+    pub struct Byte(#[allow(dead_code)] u8);
+
+    #[derive(RefCastCustom)]
+    #[ref_cast(via = [Byte])]
+    #[repr(transparent)]
+    pub struct Bytes(#[allow(dead_code)] [Byte]);
+}
+
+fn assert_custom_from<T, From>()
+where
+    T: ?Sized + ref_cast::RefCastCustomFrom<From>,
+    From: ?Sized,
+{
+}
+
+#[test]
+fn test_custom_from() {
+    assert_custom_from::<custom_from::Ident, str>();
+    assert_custom_from::<custom_from::Bytes, [custom_from::Byte]>();
+    assert_custom_from::<custom_from::Bytes, [u8]>();
+}
+
+mod capability {
+    use ref_cast::ref_cast_custom;
+    use ref_cast_test_suite::Shared;
+
+    #[ref_cast_custom]
+    pub fn ref_cast(s: &str) -> &Shared;
+
+    #[ref_cast_custom]
+    pub fn ref_cast_mut(s: &mut str) -> &mut Shared;
+}
+
+#[test]
+fn test_capability() {
+    let shared = capability::ref_cast("...");
+    assert_eq!(std::mem::size_of_val(shared), 3);
+    let mut string = String::from("...");
+    let _ = capability::ref_cast_mut(&mut string);
+}
+
+mod scope {
+    pub mod validated {
+        use ref_cast::RefCastCustom;
+
+        #[derive(RefCastCustom)]
+        #[ref_cast(scope = pub(super))]
+        #[repr(transparent)]
+        pub struct Even(u32);
+
+        impl Even {
+            pub fn get(&self) -> u32 {
+                self.0
+            }
+        }
+    }
+
+    use ref_cast::ref_cast_custom;
+    use validated::Even;
+
+    #[ref_cast_custom]
+    fn new_unchecked(n: &u32) -> &Even;
+
+    pub fn new(n: &u32) -> Option<&Even> {
+        if n % 2 == 0 {
+            Some(new_unchecked(n))
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_scope() {
+    assert_eq!(scope::new(&2).map(scope::validated::Even::get), Some(2));
+    assert!(scope::new(&3).is_none());
+}
+
+mod family {
+    use ref_cast::{ref_cast_custom, RefCastCustom};
+
+    #[derive(RefCastCustom)]
+    #[repr(transparent)]
+    pub struct Frame([u8]);
+
+    impl Frame {
+        /// Views bytes as a frame.
+        #[ref_cast_custom(mut = new_mut)]
+        pub const fn new(bytes: &[u8]) -> &Self;
+
+        pub fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        pub fn clear(&mut self) {
+            self.0.fill(0);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Frame {
+        #[ref_cast_custom(boxed = from_box, rc = from_rc, arc = from_arc)]
+        pub fn from_ref(bytes: &[u8]) -> &Self;
+    }
+}
+
+#[test]
+fn test_family() {
+    const FRAME: &family::Frame = family::Frame::new(b"...");
+    assert_eq!(FRAME.len(), 3);
+
+    let mut bytes = *b"...";
+    family::Frame::new_mut(&mut bytes).clear();
+    assert_eq!(bytes, [0; 3]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_family_smart_pointers() {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    assert_eq!(family::Frame::from_ref(b"..").len(), 2);
+
+    let mut frame = family::Frame::from_box(Box::from(&b"..."[..]));
+    frame.clear();
+    assert_eq!(frame.len(), 3);
+
+    let rc = Rc::<[u8]>::from(&b"...."[..]);
+    let frame = family::Frame::from_rc(Rc::clone(&rc));
+    assert_eq!(frame.len(), 4);
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(frame);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let frame = family::Frame::from_arc(Arc::from(&b"."[..]));
+    assert_eq!(frame.len(), 1);
+}
+
+mod checked {
+    use ref_cast::{ref_cast_custom, RefCastCustom};
+
+    #[derive(RefCastCustom)]
+    #[repr(transparent)]
+    pub struct Ascii([u8]);
+
+    #[derive(Debug, PartialEq)]
+    pub struct NotAscii;
+
+    impl Ascii {
+        const fn is_valid(bytes: &[u8]) -> bool {
+            let mut i = 0;
+            while i < bytes.len() {
+                if bytes[i] >= 0x80 {
+                    return false;
+                }
+                i += 1;
+            }
+            true
+        }
+
+        fn validate(bytes: &[u8]) -> Result<(), NotAscii> {
+            if bytes.is_ascii() {
+                Ok(())
+            } else {
+                Err(NotAscii)
+            }
+        }
+
+        #[ref_cast_custom(check = Self::is_valid, mut = new_mut)]
+        pub const fn new(bytes: &[u8]) -> Option<&Self>;
+
+        #[ref_cast_custom(check = Self::validate)]
+        pub fn try_new(bytes: &[u8]) -> Result<&Self, NotAscii>;
+
+        #[ref_cast_custom(check = <[u8]>::is_ascii)]
+        pub fn with_slice_method(bytes: &[u8]) -> Option<&Self>;
+
+        pub fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        pub fn make_uppercase(&mut self) {
+            self.0.make_ascii_uppercase();
+        }
+    }
+}
+
+#[test]
+fn test_check() {
+    const ASCII: Option<&checked::Ascii> = checked::Ascii::new(b"ascii");
+    assert_eq!(ASCII.map(checked::Ascii::len), Some(5));
+    assert!(checked::Ascii::new(b"\xFF").is_none());
+
+    let mut bytes = *b"ascii";
+    checked::Ascii::new_mut(&mut bytes)
+        .unwrap()
+        .make_uppercase();
+    assert_eq!(bytes, *b"ASCII");
+
+    assert_eq!(checked::Ascii::try_new(b"ok").unwrap().len(), 2);
+    assert_eq!(
+        checked::Ascii::try_new(b"\xFF").map(checked::Ascii::len),
+        Err(checked::NotAscii),
+    );
+
+    assert!(checked::Ascii::with_slice_method(b"\xFF").is_none());
+}
+
+#[forbid(unsafe_code)]
+mod expr {
+    use ref_cast::{RefCast, RefCastCustom};
+
+    #[derive(RefCastCustom)]
+    #[repr(transparent)]
+    pub struct Frame(pub [u8]);
+
+    #[derive(RefCast)]
+    #[repr(transparent)]
+    pub struct Utf8([u8]);
+
+    #[derive(RefCastCustom)]
+    #[ref_cast(via = Utf8)]
+    #[repr(transparent)]
+    pub struct Ident(Utf8);
+
+    pub trait Named {
+        type Name: ?Sized;
+    }
+
+    impl Named for u8 {
+        type Name = Frame;
+    }
+
+    pub const EMPTY: &Frame = ref_cast::ref_cast!(b"" as &Frame);
+}
+
+#[test]
+fn test_ref_cast_expr() {
+    use ref_cast::ref_cast;
+
+    let mut bytes = vec![1u8, 2, 3];
+    let frame = ref_cast!(&bytes[..] as &expr::Frame);
+    assert_eq!(frame.0, [1, 2, 3]);
+
+    let frame = ref_cast!(&mut bytes[..] as &mut expr::Frame);
+    frame.0[0] = 0;
+    assert_eq!(bytes, [0, 2, 3]);
+
+    assert!(expr::EMPTY.0.is_empty());
+
+    let utf8: &expr::Utf8 = ref_cast::RefCast::ref_cast(&b"main"[..]);
+    let _: &expr::Ident = ref_cast!(utf8 as &expr::Ident);
+    let _: &expr::Ident = ref_cast!(&b"main"[..] as &expr::Ident);
+
+    let frame = ref_cast!(<[u8]>::get(&bytes, 1..).unwrap() as &<u8 as expr::Named>::Name);
+    assert_eq!(frame.0, [2, 3]);
+    let frame = ref_cast!(&<Vec<u8> as AsRef<[u8]>>::as_ref(&bytes)[1..] as &expr::Frame);
+    assert_eq!(frame.0, [2, 3]);
+}
@@ -0,0 +1,50 @@
+use ref_cast::{ref_cast_custom, RefCast, RefCastCustom, RefCastFrom};
+
+#[derive(RefCast, Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Component(u32);
+
+#[derive(RefCast)]
+#[ref_cast(via = [Component])]
+#[repr(transparent)]
+pub struct Path([Component]);
+
+#[derive(RefCastCustom)]
+#[ref_cast(via = [Component])]
+#[repr(transparent)]
+pub struct CustomPath([Component]);
+
+impl CustomPath {
+    #[ref_cast_custom]
+    fn new(components: &[u32]) -> &Self;
+
+    #[ref_cast_custom]
+    fn new_mut(components: &mut [u32]) -> &mut Self;
+
+    #[ref_cast_custom]
+    fn from_components(components: &[Component]) -> &Self;
+}
+
+#[test]
+fn test_derive() {
+    let path = Path::ref_cast_from(&[1, 2, 3][..]);
+    assert_eq!(path.0, [Component(1), Component(2), Component(3)]);
+
+    let mut raw = [1, 2, 3];
+    let path = Path::ref_cast_from_mut(&mut raw[..]);
+    path.0[2].0 = 0;
+    assert_eq!(raw, [1, 2, 0]);
+}
+
+#[test]
+fn test_custom() {
+    let path = CustomPath::new(&[1, 2]);
+    assert_eq!(path.0, [Component(1), Component(2)]);
+
+    let mut raw = [1, 2];
+    CustomPath::new_mut(&mut raw).0[0].0 = 0;
+    assert_eq!(raw, [0, 2]);
+
+    let path = CustomPath::from_components(&path.0[1..]);
+    assert_eq!(path.0, [Component(2)]);
+}
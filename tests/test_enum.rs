@@ -0,0 +1,41 @@
+use ref_cast::{ref_cast_custom, RefCast, RefCastCustom};
+use std::marker::PhantomData;
+
+#[derive(RefCast)]
+#[ref_cast(forward(Debug, PartialEq), compare(PartialEq), as_ref, deref)]
+#[repr(transparent)]
+pub enum Message {
+    Ping(String),
+}
+
+#[derive(RefCast, RefCastCustom)]
+#[repr(transparent)]
+pub enum Named<T> {
+    Variant {
+        marker: PhantomData<fn() -> T>,
+        value: T,
+    },
+}
+
+impl<T> Named<T> {
+    #[ref_cast_custom]
+    fn new(value: &T) -> &Self;
+}
+
+#[test]
+fn test_enum() {
+    let string = "ping".to_owned();
+    let message = Message::ref_cast(&string);
+    let Message::Ping(inner) = message;
+    assert_eq!(inner, "ping");
+    assert_eq!(message.len(), 4);
+    assert_eq!(format!("{message:?}"), "\"ping\"");
+    assert_eq!(message, Message::ref_cast(&string));
+    assert!(*message == string);
+
+    let mut value = 0;
+    let Named::Variant { value, .. } = Named::ref_cast_mut(&mut value);
+    *value += 1;
+    let Named::Variant { value, .. } = Named::new(value);
+    assert_eq!(*value, 1);
+}
@@ -0,0 +1,29 @@
+use ref_cast::{RefCast, RefCastExt};
+
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Name(str);
+
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Frame([u8]);
+
+#[test]
+fn test_cast_ref() {
+    let name = "main".cast_ref::<Name>();
+    assert_eq!(&name.0, "main");
+
+    let names: Vec<&Name> = ["main", "test"]
+        .into_iter()
+        .map(RefCastExt::cast_ref::<Name>)
+        .collect();
+    assert_eq!(&names[1].0, "test");
+}
+
+#[test]
+fn test_cast_mut() {
+    let mut bytes = *b"frame";
+    let frame = bytes[..].cast_mut::<Frame>();
+    frame.0[0] = b'F';
+    assert_eq!(&bytes, b"Frame");
+}
@@ -0,0 +1,44 @@
+use ref_cast::RefCast;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+#[derive(RefCast)]
+#[ref_cast(forward(Debug, Display, PartialEq, Eq, PartialOrd, Ord, Hash))]
+#[repr(transparent)]
+pub struct Name(str);
+
+#[derive(RefCast)]
+#[ref_cast(forward(Debug, PartialEq))]
+#[repr(transparent)]
+pub struct Tagged<T, Tag> {
+    value: T,
+    tag: PhantomData<Tag>,
+}
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_fmt() {
+    let name = Name::ref_cast("main");
+    assert_eq!(format!("{name:?}"), "\"main\"");
+    assert_eq!(format!("{name}"), "main");
+    assert_eq!(format!("{name:>6}"), "  main");
+
+    let tagged = Tagged::<_, ()>::ref_cast(&[1, 2]);
+    assert_eq!(format!("{tagged:?}"), "[1, 2]");
+}
+
+#[test]
+fn test_cmp() {
+    assert_eq!(Name::ref_cast("a"), Name::ref_cast("a"));
+    assert!(Name::ref_cast("a") < Name::ref_cast("b"));
+    assert_eq!(hash(Name::ref_cast("main")), hash("main"));
+
+    let tagged = Tagged::<i32, String>::ref_cast(&1);
+    assert_eq!(tagged, Tagged::ref_cast(&1));
+}
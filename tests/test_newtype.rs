@@ -0,0 +1,37 @@
+use ref_cast::RefCast;
+use std::marker::PhantomData;
+
+ref_cast::newtype! {
+    #[ref_cast(as_ref, deref, forward(Debug, Display, PartialEq))]
+    pub struct Name(str);
+
+    #[derive(Debug, PartialEq)]
+    pub(crate) struct Id(u32);
+
+    #[ref_cast(forward(Debug))]
+    pub struct Tagged<T: ?Sized, Tag>
+    where
+        Tag: 'static,
+    {
+        tag: PhantomData<Tag>,
+        value: T,
+    }
+
+    struct Generic<T>(T) where T: Copy;
+}
+
+#[test]
+fn test_newtype() {
+    let name = Name::ref_cast("main");
+    assert_eq!(name.to_string(), "main");
+    assert_eq!(name.len(), 4);
+    let s: &str = name.as_ref();
+    assert_eq!(s, "main");
+
+    assert_eq!(*Id::ref_cast(&1), Id(1));
+
+    let tagged = Tagged::<str, ()>::ref_cast("tagged");
+    assert_eq!(format!("{tagged:?}"), "\"tagged\"");
+
+    assert_eq!(Generic::ref_cast(&1).0, 1);
+}
@@ -0,0 +1,76 @@
+use ref_cast::RefCast;
+use std::future::Future;
+use std::marker::PhantomPinned;
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll, Waker};
+
+#[derive(RefCast)]
+#[ref_cast(pin)]
+#[repr(transparent)]
+pub struct Instrumented<F: ?Sized>(F);
+
+impl<F> Future for Instrumented<F>
+where
+    F: Future + ?Sized,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.project().poll(cx)
+    }
+}
+
+pub struct Pending {
+    polls: usize,
+    _pinned: PhantomPinned,
+}
+
+impl Future for Pending {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.polls += 1;
+        if this.polls < 3 {
+            Poll::Pending
+        } else {
+            Poll::Ready(this.polls)
+        }
+    }
+}
+
+fn assert_unpin<T: Unpin + ?Sized>() {}
+
+#[test]
+fn test_project() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = pin!(Instrumented(Pending {
+        polls: 0,
+        _pinned: PhantomPinned,
+    }));
+    assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(3));
+}
+
+#[test]
+fn test_pin_ref_cast() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = pin!(Pending {
+        polls: 2,
+        _pinned: PhantomPinned,
+    });
+    let instrumented = Instrumented::pin_ref_cast(future.as_mut());
+    assert_eq!(instrumented.poll(&mut cx), Poll::Ready(3));
+
+    let future: Pin<&mut dyn Future<Output = usize>> = future;
+    let instrumented: Pin<&mut Instrumented<dyn Future<Output = usize>>> =
+        Instrumented::pin_ref_cast(future);
+    assert_eq!(instrumented.project().poll(&mut cx), Poll::Ready(4));
+}
+
+#[test]
+fn test_unpin() {
+    assert_unpin::<Instrumented<std::future::Ready<()>>>();
+    assert_unpin::<Instrumented<str>>();
+}
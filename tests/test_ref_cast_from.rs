@@ -0,0 +1,44 @@
+use ref_cast::{RefCast, RefCastCustom, RefCastFrom};
+use std::mem::ManuallyDrop;
+
+#[derive(RefCast)]
+#[ref_cast(cast_from(ManuallyDrop<str>, String, str))]
+#[repr(transparent)]
+pub struct Key(str);
+
+#[derive(RefCastCustom)]
+#[ref_cast(cast_from(ManuallyDrop<[T]>, Vec<T>, [T]))]
+#[repr(transparent)]
+pub struct Custom<T>([T]);
+
+#[test]
+fn test_derive() {
+    let key: &Key = ref_cast::cast("key");
+    assert_eq!(&key.0, "key");
+
+    // ManuallyDrop<str> is repr(transparent) around str.
+    let manually_drop = unsafe { &*("key" as *const str as *const ManuallyDrop<str>) };
+    let key = ref_cast::cast::<Key, ManuallyDrop<str>>(manually_drop);
+    assert_eq!(&key.0, "key");
+
+    let mut string = String::from("key");
+    let key: &mut Key = ref_cast::cast_mut(&mut string);
+    key.0.make_ascii_uppercase();
+    assert_eq!(string, "KEY");
+}
+
+#[test]
+fn test_custom() {
+    let mut vec = vec![1, 2, 3];
+    let custom = Custom::ref_cast_from_mut(&mut vec);
+    custom.0[0] = 0;
+    assert_eq!(vec, [0, 2, 3]);
+
+    let custom: &Custom<i32> = ref_cast::cast(&vec[1..]);
+    assert_eq!(custom.0, [2, 3]);
+
+    let array = ManuallyDrop::new([1, 2, 3]);
+    let manually_drop: &ManuallyDrop<[i32]> = &array;
+    let custom = ref_cast::cast::<Custom<i32>, ManuallyDrop<[i32]>>(manually_drop);
+    assert_eq!(custom.0, [1, 2, 3]);
+}
@@ -0,0 +1,71 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast)]
+#[ref_cast(slice)]
+#[repr(transparent)]
+pub struct Frame([u8]);
+
+#[derive(RefCast)]
+#[ref_cast(slice)]
+#[repr(transparent)]
+pub struct Name(str);
+
+#[derive(RefCast)]
+#[ref_cast(slice)]
+#[repr(transparent)]
+pub struct Generic<T>([T]);
+
+#[test]
+fn test_index() {
+    let frame = Frame::ref_cast(b"frame");
+    assert_eq!(&frame[1..3].0, b"ra");
+    assert_eq!(&frame[3..].0, b"me");
+    assert_eq!(&frame[..=1].0, b"fr");
+    assert_eq!(&frame[..].0, b"frame");
+
+    let mut bytes = *b"frame";
+    let frame = Frame::ref_cast_mut(&mut bytes);
+    frame[1..].0[0] = b'l';
+    assert_eq!(&bytes, b"flame");
+
+    let name = Name::ref_cast("name");
+    assert_eq!(&name[1..3].0, "am");
+}
+
+#[test]
+fn test_get() {
+    let name = Name::ref_cast("name");
+    assert_eq!(name.get(..2).map(|name| &name.0), Some("na"));
+    assert!(name.get(..5).is_none());
+
+    let mut values = [1, 2, 3];
+    let generic = Generic::ref_cast_mut(&mut values);
+    generic.get_mut(1..).unwrap().0[0] = 0;
+    assert_eq!(values, [1, 0, 3]);
+}
+
+#[test]
+fn test_split() {
+    let frame = Frame::ref_cast(b"frame");
+    let (head, tail) = frame.split_at(2);
+    assert_eq!((&head.0, &tail.0), (b"fr" as &[u8], b"ame" as &[u8]));
+
+    let (first, rest) = frame.split_first().unwrap();
+    assert_eq!((*first, &rest.0), (b'f', b"rame" as &[u8]));
+    let (last, rest) = frame.split_last().unwrap();
+    assert_eq!((*last, &rest.0), (b'e', b"fram" as &[u8]));
+    assert!(frame[..0].split_first().is_none());
+
+    let name = Name::ref_cast("ñame");
+    let (first, rest) = name.split_first().unwrap();
+    assert_eq!((first, &rest.0), ('ñ', "ame"));
+    let (last, rest) = name.split_last().unwrap();
+    assert_eq!((last, &rest.0), ('e', "ñam"));
+    assert!(name[..0].split_last().is_none());
+
+    let mut values = [1, 2, 3];
+    let generic = Generic::ref_cast_mut(&mut values);
+    let (first, rest) = generic.split_first_mut().unwrap();
+    *first = rest.0[1];
+    assert_eq!(values, [3, 2, 3]);
+}
@@ -0,0 +1,32 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast, Debug, PartialEq)]
+#[repr(transparent)]
+pub struct NodeId(u32);
+
+fn generic<T: RefCast + ?Sized>(from: &T::From) -> &T {
+    T::ref_cast(from)
+}
+
+#[test]
+fn test_slice() {
+    let ids = <[NodeId]>::ref_cast(&[1, 2, 3]);
+    assert_eq!(ids, [NodeId(1), NodeId(2), NodeId(3)]);
+
+    let mut raw = [1, 2, 3];
+    let ids = <[NodeId]>::ref_cast_mut(&mut raw);
+    ids[0].0 = 0;
+    assert_eq!(raw, [0, 2, 3]);
+
+    let ids: &[NodeId] = generic(&raw[1..]);
+    assert_eq!(ids, [NodeId(2), NodeId(3)]);
+}
+
+#[test]
+fn test_array() {
+    let ids = <[NodeId; 2]>::ref_cast(&[1, 2]);
+    assert_eq!(ids, &[NodeId(1), NodeId(2)]);
+
+    let nested: &[[NodeId; 2]] = generic(&[[1, 2], [3, 4]][..]);
+    assert_eq!(nested[1], [NodeId(3), NodeId(4)]);
+}
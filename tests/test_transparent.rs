@@ -0,0 +1,34 @@
+use ref_cast::{ref_cast_custom, RefCast};
+
+#[ref_cast::transparent]
+#[ref_cast(as_ref, forward(Debug))]
+pub struct Name(str);
+
+#[ref_cast::transparent(RefCastCustom)]
+pub struct Frame([u8]);
+
+impl Frame {
+    #[ref_cast_custom]
+    fn new(bytes: &[u8]) -> &Self;
+}
+
+#[ref_cast::transparent]
+#[repr(transparent)]
+pub struct Explicit<T>(T);
+
+#[ref_cast::transparent]
+#[repr(C)]
+pub struct ReprC(u8);
+
+#[test]
+fn test_transparent() {
+    let name = Name::ref_cast("main");
+    assert_eq!(format!("{name:?}"), "\"main\"");
+    let s: &str = name.as_ref();
+    assert_eq!(s, "main");
+
+    assert_eq!(Frame::new(b"frame").0, *b"frame");
+
+    assert_eq!(Explicit::ref_cast(&1).0, 1);
+    assert_eq!(ReprC::ref_cast(&1).0, 1);
+}
@@ -50,3 +50,18 @@ fn test_trivial() {
     Override::<u8, i8>::ref_cast(&PhantomData::<i8>);
     Unsized::ref_cast("...");
 }
+
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct ExplicitFrom<T> {
+    pub marker: Marker,
+    #[ref_cast(from)]
+    pub value: T,
+    pub unit: (),
+}
+
+#[test]
+fn test_explicit_from() {
+    let value = ExplicitFrom::ref_cast(&0);
+    assert_eq!(value.value, 0);
+}
@@ -0,0 +1,91 @@
+use ref_cast::RefCast;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(RefCast)]
+#[ref_cast(unsize)]
+#[repr(transparent)]
+pub struct Buffer<T: ?Sized>(T);
+
+#[derive(RefCast)]
+#[ref_cast(unsize)]
+#[repr(transparent)]
+pub struct Tagged<'a, Tag, T>
+where
+    T: ?Sized + Debug,
+{
+    tag: PhantomData<&'a Tag>,
+    value: T,
+}
+
+fn sum(buffer: &Buffer<[u8]>) -> u32 {
+    buffer.0.iter().map(|&byte| u32::from(byte)).sum()
+}
+
+#[test]
+fn test_unsize() {
+    let buffer: &Buffer<[u8; 4]> = Buffer::ref_cast(&[1, 2, 3, 4]);
+    assert_eq!(sum(buffer.unsize()), 10);
+
+    let mut array = [1, 2, 3, 4];
+    let buffer: &mut Buffer<[u8; 4]> = Buffer::ref_cast_mut(&mut array);
+    buffer.unsize_mut().0[0] = 0;
+    assert_eq!(array, [0, 2, 3, 4]);
+}
+
+#[test]
+fn test_generics() {
+    let tagged = Tagged::<(), [i32; 2]>::ref_cast(&[1, 2]);
+    let tagged: &Tagged<(), [i32]> = tagged.unsize();
+    assert_eq!(tagged.value.len(), 2);
+}
+
+pub trait Handler {
+    fn handle(&mut self, request: &str) -> usize;
+}
+
+pub struct Counter {
+    count: usize,
+    padding: [u64; 3],
+}
+
+impl Handler for Counter {
+    fn handle(&mut self, request: &str) -> usize {
+        self.count += request.len();
+        self.count
+    }
+}
+
+#[derive(RefCast)]
+#[ref_cast(unsize = dyn Handler)]
+#[repr(transparent)]
+pub struct Instrumented<H: ?Sized>(H);
+
+impl Instrumented<dyn Handler> {
+    fn call(&mut self, request: &str) -> usize {
+        self.0.handle(request)
+    }
+}
+
+#[test]
+fn test_as_dyn() {
+    let mut counter = Instrumented(Counter {
+        count: 0,
+        padding: [0; 3],
+    });
+    assert_eq!(counter.as_dyn_mut().call("main"), 4);
+    let instrumented: &Instrumented<dyn Handler> = counter.as_dyn();
+    assert_eq!(std::mem::size_of_val(instrumented), 32);
+    assert_eq!(counter.0.padding, [0; 3]);
+}
+
+#[test]
+fn test_boxed_dyn() {
+    let mut handlers: Vec<Box<dyn Handler>> = vec![Box::new(Counter {
+        count: 1,
+        padding: [0; 3],
+    })];
+    let instrumented = Instrumented::ref_cast_mut(&mut *handlers[0]);
+    assert_eq!(std::mem::size_of_val(instrumented), 32);
+    assert_eq!(instrumented.call("lib"), 4);
+}
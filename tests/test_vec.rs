@@ -0,0 +1,41 @@
+#![cfg(feature = "alloc")]
+
+use ref_cast::{cast_vec, uncast_vec, RefCast};
+use std::marker::PhantomData;
+
+#[derive(RefCast, Debug, PartialEq)]
+#[repr(transparent)]
+pub struct NodeId(u32);
+
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Tagged<T> {
+    value: String,
+    #[trivial]
+    tag: PhantomData<T>,
+}
+
+#[test]
+fn test_round_trip() {
+    let mut decoded = Vec::with_capacity(8);
+    decoded.extend([3, 1, 4]);
+    let ptr = decoded.as_ptr() as usize;
+
+    let ids: Vec<NodeId> = cast_vec(decoded);
+    assert_eq!(ids, [NodeId(3), NodeId(1), NodeId(4)]);
+    assert_eq!(ids.as_ptr() as usize, ptr);
+    assert_eq!(ids.capacity(), 8);
+
+    let decoded: Vec<u32> = uncast_vec(ids);
+    assert_eq!(decoded, [3, 1, 4]);
+    assert_eq!(decoded.as_ptr() as usize, ptr);
+}
+
+#[test]
+fn test_drop() {
+    let strings = vec!["a".to_owned(), "b".to_owned()];
+    let tagged: Vec<Tagged<()>> = cast_vec(strings);
+    assert_eq!(tagged[1].value, "b");
+    let strings = uncast_vec(tagged);
+    assert_eq!(strings, ["a", "b"]);
+}
@@ -0,0 +1,46 @@
+use ref_cast::{RefCast, RefCastFrom};
+
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Raw([u8]);
+
+#[derive(RefCast)]
+#[ref_cast(via = Raw)]
+#[repr(transparent)]
+pub struct Utf8(Raw);
+
+#[derive(RefCast)]
+#[ref_cast(via = Utf8, via = Raw)]
+#[repr(transparent)]
+pub struct Ident(Utf8);
+
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Inner<T>(T);
+
+#[derive(RefCast)]
+#[ref_cast(via = Inner<T>)]
+#[repr(transparent)]
+pub struct Outer<T>(Inner<T>);
+
+#[test]
+fn test_via() {
+    let ident = Ident::ref_cast_from(&b"main"[..]);
+    assert_eq!(&ident.0 .0 .0, b"main");
+
+    let ident: &Ident = RefCastFrom::<Raw>::ref_cast_from(Raw::ref_cast(b"main"));
+    assert_eq!(&ident.0 .0 .0, b"main");
+
+    let mut bytes = *b"main";
+    let ident = Ident::ref_cast_from_mut(&mut bytes[..]);
+    ident.0 .0 .0[0] = b'p';
+    assert_eq!(&bytes, b"pain");
+}
+
+#[test]
+fn test_generic() {
+    let mut value = 0;
+    let outer = Outer::ref_cast_from_mut(&mut value);
+    outer.0 .0 += 1;
+    assert_eq!(value, 1);
+}
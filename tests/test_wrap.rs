@@ -0,0 +1,59 @@
+use ref_cast::RefCast;
+use std::marker::{PhantomData, PhantomPinned};
+
+mod private {
+    use ref_cast::RefCast;
+    use std::marker::PhantomData;
+
+    #[derive(RefCast)]
+    #[ref_cast(wrap)]
+    #[repr(transparent)]
+    pub struct Tagged<Tag, T: ?Sized> {
+        tag: PhantomData<Tag>,
+        value: T,
+    }
+
+    impl<Tag, T: ?Sized> Tagged<Tag, T> {
+        pub fn value(&self) -> &T {
+            &self.value
+        }
+    }
+}
+
+#[derive(RefCast)]
+#[ref_cast(wrap)]
+#[repr(transparent)]
+pub struct Pinned(String, (), PhantomPinned);
+
+#[derive(RefCast)]
+#[ref_cast(wrap)]
+#[repr(transparent)]
+pub enum Either {
+    Left {
+        value: Vec<u8>,
+        marker: PhantomData<u8>,
+    },
+}
+
+#[test]
+fn test_wrap_private_marker() {
+    let tagged = private::Tagged::<(), _>::wrap(1);
+    assert_eq!(*tagged.value(), 1);
+    assert_eq!(tagged.into_inner(), 1);
+
+    let unsized_field: &private::Tagged<(), str> = private::Tagged::ref_cast("main");
+    assert_eq!(unsized_field.value(), "main");
+}
+
+#[test]
+fn test_wrap_tuple() {
+    let pinned = Pinned::wrap("main".to_owned());
+    assert_eq!(pinned.0, "main");
+    assert_eq!(pinned.into_inner(), "main");
+}
+
+#[test]
+fn test_wrap_enum() {
+    let either = Either::wrap(vec![1]);
+    assert_eq!(either.into_inner(), [1]);
+}
@@ -0,0 +1,12 @@
+use ref_cast::{ref_cast_custom, RefCastCustom};
+
+#[derive(RefCastCustom)]
+#[repr(C, packed)]
+pub struct Packed(u32);
+
+impl Packed {
+    #[ref_cast_custom(boxed = from_box)]
+    pub fn from_ref(n: &u32) -> &Self;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast)]
+#[repr(C, packed)]
+pub struct Packed(u32);
+
+fn main() {
+    let _: Vec<Packed> = ref_cast::cast_vec(vec![1u32, 2, 3]);
+    let _: Vec<u32> = ref_cast::uncast_vec(Vec::<Packed>::new());
+}
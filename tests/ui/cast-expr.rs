@@ -0,0 +1,25 @@
+use ref_cast::{ref_cast, RefCastCustom};
+use ref_cast_test_suite::Struct;
+
+#[derive(RefCastCustom)]
+#[repr(transparent)]
+pub struct Frame([u8]);
+
+fn foreign(s: &str) -> &Struct {
+    ref_cast!(s as &Struct)
+}
+
+fn wrong_source(s: &str) -> &Frame {
+    ref_cast!(s as &Frame)
+}
+
+fn short_lifetime() -> &'static Frame {
+    let bytes = vec![0u8];
+    ref_cast!(&bytes[..] as &Frame)
+}
+
+fn not_reference(bytes: &[u8]) {
+    let _ = ref_cast!(bytes as Frame);
+}
+
+fn main() {}
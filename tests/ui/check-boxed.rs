@@ -0,0 +1,12 @@
+use ref_cast::{ref_cast_custom, RefCastCustom};
+
+#[derive(RefCastCustom)]
+#[repr(transparent)]
+pub struct Ascii([u8]);
+
+impl Ascii {
+    #[ref_cast_custom(check = <[u8]>::is_ascii, boxed = new_boxed)]
+    pub fn new(bytes: &[u8]) -> Option<&Self>;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ref_cast::{RefCastCustom, RefCastFrom};
+
+#[derive(RefCastCustom)]
+#[ref_cast(cast_from(String))]
+#[repr(transparent)]
+pub struct Key(str);
+
+fn main() {
+    let _ = Key::ref_cast_from("key");
+}
@@ -0,0 +1,7 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast)]
+#[repr(transparent)]
+enum Empty {}
+
+fn main() {}
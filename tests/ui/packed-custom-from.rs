@@ -0,0 +1,11 @@
+use ref_cast::{RefCastCustom, RefCastCustomFrom};
+
+#[derive(RefCastCustom)]
+#[repr(C, packed)]
+pub struct Packed(u32);
+
+fn assert_custom_from<T: RefCastCustomFrom<u32>>() {}
+
+fn main() {
+    assert_custom_from::<Packed>();
+}
@@ -0,0 +1,10 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast)]
+#[repr(C, packed)]
+pub struct Packed(u32);
+
+fn main() {
+    let _ = Packed::ref_cast(&0);
+    let _ = <[Packed]>::ref_cast(&[0u32][..]);
+}
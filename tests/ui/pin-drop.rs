@@ -0,0 +1,12 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast)]
+#[ref_cast(pin)]
+#[repr(transparent)]
+pub struct Wrapper<F>(F);
+
+impl<F> Drop for Wrapper<F> {
+    fn drop(&mut self) {}
+}
+
+fn main() {}
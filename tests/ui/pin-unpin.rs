@@ -0,0 +1,10 @@
+use ref_cast::RefCast;
+
+#[derive(RefCast)]
+#[ref_cast(pin)]
+#[repr(transparent)]
+pub struct Wrapper<F>(F);
+
+impl<F> Unpin for Wrapper<F> {}
+
+fn main() {}
@@ -0,0 +1,21 @@
+mod validated {
+    use ref_cast::RefCastCustom;
+
+    #[derive(RefCastCustom)]
+    #[ref_cast(scope = pub(self), cast_from(String))]
+    #[repr(transparent)]
+    pub struct Ascii(str);
+}
+
+mod shared {
+    use ref_cast::RefCastCustom;
+
+    pub struct Capability {}
+
+    #[derive(RefCastCustom)]
+    #[ref_cast(capability = Capability, cast_from(str))]
+    #[repr(transparent)]
+    pub struct Shared(str);
+}
+
+fn main() {}
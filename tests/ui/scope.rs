@@ -0,0 +1,31 @@
+mod validated {
+    use ref_cast::{ref_cast_custom, RefCastCustom};
+
+    #[derive(RefCastCustom)]
+    #[ref_cast(scope = pub(self))]
+    #[repr(transparent)]
+    pub struct Ascii(str);
+
+    impl Ascii {
+        #[ref_cast_custom]
+        fn new_unchecked(s: &str) -> &Self;
+
+        pub fn new(s: &str) -> Option<&Self> {
+            if s.is_ascii() {
+                Some(Self::new_unchecked(s))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+mod elsewhere {
+    use super::validated::Ascii;
+    use ref_cast::ref_cast_custom;
+
+    #[ref_cast_custom]
+    fn ascii(s: &str) -> &Ascii;
+}
+
+fn main() {}
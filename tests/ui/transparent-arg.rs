@@ -0,0 +1,4 @@
+#[ref_cast::transparent(Transparent)]
+pub struct Name(str);
+
+fn main() {}
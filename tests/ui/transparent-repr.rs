@@ -0,0 +1,5 @@
+#[ref_cast::transparent]
+#[repr(align(4))]
+pub struct Aligned(u8);
+
+fn main() {}
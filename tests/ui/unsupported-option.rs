@@ -0,0 +1,18 @@
+use ref_cast::{RefCast, RefCastCustom};
+
+#[derive(RefCastCustom)]
+#[ref_cast(forward(Debug))]
+#[repr(transparent)]
+pub struct Custom(str);
+
+#[derive(RefCastCustom)]
+#[ref_cast(deref)]
+#[repr(transparent)]
+pub struct Deref(str);
+
+#[derive(RefCast)]
+#[ref_cast(scope = pub(self))]
+#[repr(transparent)]
+pub struct Scoped(str);
+
+fn main() {}